@@ -0,0 +1,360 @@
+use snafu::prelude::*;
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Identifies a puzzle input without needing to store it.
+///
+/// Everyone gets their own input, so answers are only meaningful
+/// alongside the input they were computed from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputHash(u64);
+
+impl InputHash {
+    /// FNV-1a, which is stable across platforms and Rust versions.
+    pub fn of(input: &str) -> Self {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let hash = input
+            .bytes()
+            .fold(OFFSET_BASIS, |h, b| (h ^ u64::from(b)).wrapping_mul(PRIME));
+
+        Self(hash)
+    }
+}
+
+impl fmt::Display for InputHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl serde::Serialize for InputHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for InputHash {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(Self)
+    }
+}
+
+/// How a computed answer compares to the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => "correct".fmt(f),
+            Self::Wrong { expected } if expected.contains('\n') => {
+                write!(f, "wrong, expected:\n{expected}")
+            }
+            Self::Wrong { expected } => write!(f, "wrong, expected {expected}"),
+            Self::Unknown => "unknown".fmt(f),
+        }
+    }
+}
+
+/// Confirmed answers for every input that has been solved.
+///
+/// Stored as one line per answer: the input hash, the part, and the
+/// answer, with backslashes and newlines escaped. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(InputHash, u8), String>,
+}
+
+impl Answers {
+    /// Reads the answers file. A missing file has no answers in it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AnswersError> {
+        use answers_error::*;
+
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse().context(MalformedSnafu { path }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context(ReadSnafu { path }),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AnswersError> {
+        use answers_error::*;
+
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).context(WriteSnafu { path })
+    }
+
+    pub fn get(&self, hash: InputHash, part: u8) -> Option<&str> {
+        self.entries.get(&(hash, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, hash: InputHash, part: u8, answer: &str) -> Verdict {
+        match self.get(hash, part) {
+            Some(expected) if expected == answer.trim_end() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Returns the previously recorded answer, if any.
+    pub fn record(&mut self, hash: InputHash, part: u8, answer: &str) -> Option<String> {
+        self.entries
+            .insert((hash, part), answer.trim_end().to_owned())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseAnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use parse_answers_error::*;
+
+        let mut entries = BTreeMap::new();
+
+        for (line, l) in (1_usize..).zip(s.lines()) {
+            if l.trim().is_empty() || l.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = l.splitn(3, ' ');
+            let hash = parts.next().context(HashMissingSnafu { line })?;
+            let part = parts.next().context(PartMissingSnafu { line })?;
+            let answer = parts.next().context(AnswerMissingSnafu { line })?;
+
+            let hash = hash.parse().context(HashInvalidSnafu { line })?;
+            let part = part.parse().context(PartInvalidSnafu { line })?;
+            let answer = unescape(answer).context(EscapeInvalidSnafu { line })?;
+
+            entries.insert((hash, part), answer);
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((hash, part), answer) in &self.entries {
+            writeln!(f, "{hash} {part} {}", escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', r"\\").replace('\n', r"\n")
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                '\\' => out.push('\\'),
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+
+    Some(out)
+}
+
+/// Compares each part's answer against the recorded answers for one
+/// input, optionally recording them as confirmed.
+#[derive(Debug)]
+pub struct AnswerCheck {
+    path: PathBuf,
+    answers: Answers,
+    hash: InputHash,
+    confirm: bool,
+    changed: bool,
+    wrong: Vec<u8>,
+}
+
+impl AnswerCheck {
+    pub fn new(path: impl Into<PathBuf>, input: &str, confirm: bool) -> Result<Self, AnswersError> {
+        let path = path.into();
+        let answers = Answers::load(&path)?;
+
+        Ok(Self {
+            path,
+            answers,
+            hash: InputHash::of(input),
+            confirm,
+            changed: false,
+            wrong: Vec::new(),
+        })
+    }
+
+    pub fn hash(&self) -> InputHash {
+        self.hash
+    }
+
+    /// Checks the answer, recording it when confirming.
+    pub fn check(&mut self, part: u8, answer: &str) -> Verdict {
+        let verdict = self.answers.check(self.hash, part, answer);
+
+        if self.confirm {
+            if verdict != Verdict::Correct {
+                self.answers.record(self.hash, part, answer);
+                self.changed = true;
+            }
+        } else if let Verdict::Wrong { .. } = verdict {
+            self.wrong.push(part);
+        }
+
+        verdict
+    }
+
+    /// Prints the answer on standard output and the verdict on
+    /// standard error.
+    pub fn report(&mut self, part: u8, answer: impl fmt::Display) {
+        let answer = answer.to_string();
+        let verdict = self.check(part, &answer);
+
+        println!("{}", answer.trim_end());
+        match verdict {
+            Verdict::Unknown if self.confirm => eprintln!("Part {part}: recorded"),
+            Verdict::Wrong { .. } if self.confirm => eprintln!("Part {part}: recorded ({verdict})"),
+            Verdict::Unknown => eprintln!("Part {part}: unknown (use --confirm to record it)"),
+            verdict => eprintln!("Part {part}: {verdict}"),
+        }
+    }
+
+    /// Saves any newly confirmed answers and fails if any answer was
+    /// wrong.
+    pub fn finish(self) -> Result<(), AnswersError> {
+        use answers_error::*;
+
+        if self.changed {
+            self.answers.save(&self.path)?;
+        }
+
+        ensure!(
+            self.wrong.is_empty(),
+            WrongSnafu {
+                parts: self.wrong,
+                hash: self.hash,
+            }
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum AnswersError {
+    #[snafu(display("Could not read the answers from {}", path.display()))]
+    Read { source: io::Error, path: PathBuf },
+
+    #[snafu(display("The answers in {} are malformed", path.display()))]
+    Malformed {
+        source: ParseAnswersError,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not write the answers to {}", path.display()))]
+    Write { source: io::Error, path: PathBuf },
+
+    #[snafu(display("Wrong answer for part(s) {parts:?} of input {hash}"))]
+    Wrong { parts: Vec<u8>, hash: InputHash },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseAnswersError {
+    #[snafu(display("Line {line} is missing the input hash"))]
+    HashMissing { line: usize },
+
+    #[snafu(display("Line {line} is missing the part"))]
+    PartMissing { line: usize },
+
+    #[snafu(display("Line {line} is missing the answer"))]
+    AnswerMissing { line: usize },
+
+    #[snafu(display("Line {line} has an invalid input hash"))]
+    HashInvalid {
+        source: std::num::ParseIntError,
+        line: usize,
+    },
+
+    #[snafu(display("Line {line} has an invalid part"))]
+    PartInvalid {
+        source: std::num::ParseIntError,
+        line: usize,
+    },
+
+    #[snafu(display("Line {line} has an invalid escape sequence"))]
+    EscapeInvalid { line: usize },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!("cbf29ce484222325", InputHash::of("").to_string());
+        assert_eq!("af63dc4c8601ec8c", InputHash::of("a").to_string());
+    }
+
+    #[test]
+    #[snafu::report]
+    fn round_trip() -> Result<(), ParseAnswersError> {
+        let hash = InputHash::of("input");
+        let mut answers = Answers::default();
+        answers.record(hash, 1, "42");
+        answers.record(hash, 2, "#.#\n.#.\\\n");
+
+        let reparsed = answers.to_string().parse::<Answers>()?;
+
+        assert_eq!(answers, reparsed);
+        assert_eq!(Some("#.#\n.#.\\"), reparsed.get(hash, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn verdicts() {
+        let hash = InputHash::of("input");
+        let mut answers = Answers::default();
+        answers.record(hash, 1, "42");
+
+        assert_eq!(Verdict::Correct, answers.check(hash, 1, "42"));
+        assert_eq!(
+            Verdict::Wrong {
+                expected: "42".into()
+            },
+            answers.check(hash, 1, "99"),
+        );
+        assert_eq!(Verdict::Unknown, answers.check(hash, 2, "42"));
+        assert_eq!(
+            Verdict::Unknown,
+            answers.check(InputHash::of("other"), 1, "42")
+        );
+    }
+}
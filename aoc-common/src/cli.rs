@@ -0,0 +1,83 @@
+use crate::{
+    answers::AnswerCheck, input::InputSource, output::Format, AnswersError, InputError, Output,
+    Record, VisualizeArgs,
+};
+use clap::Parser;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// The command line accepted by each day's binary.
+#[derive(Debug, Parser)]
+pub struct DayArgs {
+    /// Read the puzzle input from this file (`-` for stdin) instead of
+    /// the day's bundled input
+    pub input: Option<PathBuf>,
+
+    /// Record this run's answers as the confirmed answers for the input
+    #[arg(long)]
+    pub confirm: bool,
+
+    /// How to print the answers
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+impl DayArgs {
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    pub fn read_input(&self, default: impl AsRef<Path>) -> Result<String, InputError> {
+        let arg = self.input.clone().map(PathBuf::into_os_string);
+        InputSource::from_arg(arg, default).read()
+    }
+
+    pub fn output(
+        &self,
+        day: u8,
+        answers_path: impl Into<PathBuf>,
+        input: &str,
+    ) -> Result<Output, AnswersError> {
+        let answers = AnswerCheck::new(answers_path, input, self.confirm)?;
+        Ok(Output::new(day, self.format, self.confirm, answers))
+    }
+
+    /// When printing JSON, reports a failure of the whole day as a
+    /// record without a part, as `aoc run` does. Failures that
+    /// `in_part` picks out have already been reported by
+    /// [`Output::solve`].
+    pub fn report_failure<E>(
+        &self,
+        day: u8,
+        result: Result<(), E>,
+        in_part: impl FnOnce(&E) -> bool,
+    ) -> Result<(), E>
+    where
+        E: Error + 'static,
+    {
+        if let Err(e) = &result {
+            if self.format == Format::Json && !in_part(e) {
+                Record::error(day, None, None, e).print();
+            }
+        }
+        result
+    }
+}
+
+/// The command line accepted by the days that can draw their working.
+#[derive(Debug, Parser)]
+pub struct VisualDayArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+
+    #[command(flatten)]
+    pub visualize: VisualizeArgs,
+}
+
+impl VisualDayArgs {
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+}
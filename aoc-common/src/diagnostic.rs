@@ -0,0 +1,262 @@
+use std::{error::Error, fmt};
+
+/// A position in the puzzle input, along with the line it is on, so
+/// that a parse error can point at what it didn't like.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based
+    pub line: usize,
+    /// 1-based, counted in characters
+    pub column: usize,
+    /// How many characters to underline
+    pub width: usize,
+    /// The complete line, without the line ending
+    pub text: String,
+}
+
+impl Location {
+    /// Finds where `span` is inside of `input`.
+    ///
+    /// # Panics
+    ///
+    /// `span` must be a slice of `input`.
+    pub fn of(input: &str, span: &str) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&o| o + span.len() <= input.len())
+            .expect("The span is not part of the input");
+
+        let (before, after) = input.split_at(offset);
+
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = after.find('\n').map_or(input.len(), |i| offset + i);
+
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        let text = input[line_start..line_end].trim_end_matches('\r');
+
+        let in_line = &span[..span.len().min(line_end - offset)];
+        let width = in_line.chars().count().max(1);
+
+        Self {
+            line,
+            column,
+            width,
+            text: text.to_owned(),
+        }
+    }
+
+    /// Converts a location relative to `part` into one relative to
+    /// `input`, which `part` is a slice of.
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let outer = Self::of(input, part);
+
+        if self.line == 1 {
+            Self {
+                line: outer.line,
+                column: outer.column + self.column - 1,
+                width: self.width,
+                text: outer.text,
+            }
+        } else {
+            Self {
+                line: outer.line + self.line - 1,
+                ..self
+            }
+        }
+    }
+
+    /// Converts a location relative to a single line into one relative
+    /// to the whole input, where that line is line number `line`.
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: self.line + line - 1,
+            ..self
+        }
+    }
+
+    /// The line with the location underlined, in the style of `rustc`.
+    pub fn snippet(&self) -> Snippet<'_> {
+        Snippet(self)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+pub struct Snippet<'a>(&'a Location);
+
+impl fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Location {
+            line,
+            column,
+            width,
+            ref text,
+        } = *self.0;
+
+        let gutter = line.to_string().len();
+        let blank = "";
+
+        writeln!(f, "{blank:gutter$} |")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(
+            f,
+            "{blank:gutter$} | {blank:indent$}{carets}",
+            indent = column - 1,
+            carets = "^".repeat(width),
+        )
+    }
+}
+
+/// An error along with where in the input it happened.
+#[derive(Debug, Clone)]
+pub struct Located<E> {
+    pub location: Location,
+    pub source: E,
+}
+
+impl<E> Located<E> {
+    pub fn new(input: &str, span: &str, source: E) -> Self {
+        Self {
+            location: Location::of(input, span),
+            source,
+        }
+    }
+
+    /// Wraps the error in another, keeping its location.
+    pub fn map<F>(self, f: impl FnOnce(E) -> F) -> Located<F> {
+        Located {
+            location: self.location,
+            source: f(self.source),
+        }
+    }
+
+    /// See [`Location::within`].
+    pub fn within(self, input: &str, part: &str) -> Self {
+        Self {
+            location: self.location.within(input, part),
+            ..self
+        }
+    }
+
+    /// See [`Location::on_line`].
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            location: self.location.on_line(line),
+            ..self
+        }
+    }
+}
+
+impl<E> fmt::Display for Located<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "At {}:", self.location)?;
+        self.location.snippet().fmt(f)
+    }
+}
+
+impl<E> Error for Located<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Attaches locations to errors, only doing the work when there is an
+/// error.
+pub trait LocateExt<T, E> {
+    /// `span` must be a slice of `input`.
+    fn locate(self, input: &str, span: &str) -> Result<T, Located<E>>;
+}
+
+impl<T, E> LocateExt<T, E> for Result<T, E> {
+    fn locate(self, input: &str, span: &str) -> Result<T, Located<E>> {
+        self.map_err(|e| Located::new(input, span, e))
+    }
+}
+
+/// Converts errors located relative to a slice of the input into ones
+/// located relative to the whole input.
+pub trait WithinExt<T, E> {
+    /// `part` must be a slice of `input`.
+    fn within(self, input: &str, part: &str) -> Result<T, Located<E>>;
+
+    /// For input read one line at a time, where the error was located
+    /// relative to line number `line`.
+    fn on_line(self, line: usize) -> Result<T, Located<E>>;
+}
+
+impl<T, E> WithinExt<T, E> for Result<T, Located<E>> {
+    fn within(self, input: &str, part: &str) -> Result<T, Located<E>> {
+        self.map_err(|e| e.within(input, part))
+    }
+
+    fn on_line(self, line: usize) -> Result<T, Located<E>> {
+        self.map_err(|e| e.on_line(line))
+    }
+}
+
+/// An empty span just past the end of `s`, for pointing at where
+/// something was expected but missing.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let input = "first\nsecond line\r\nthird";
+
+        let second = input.find("second").unwrap();
+        let line = &input[second..][.."second line".len()];
+        let word = &line["second ".len()..];
+
+        assert_eq!(
+            Location {
+                line: 2,
+                column: 8,
+                width: 4,
+                text: "second line".into(),
+            },
+            Location::of(input, word),
+        );
+
+        assert_eq!(
+            Location {
+                line: 3,
+                column: 6,
+                width: 1,
+                text: "third".into(),
+            },
+            Location::of(input, end_of(input)),
+        );
+
+        let relative = Location::of(line, word).within(input, line);
+        assert_eq!(Location::of(input, word), relative);
+
+        let relative = Location::of(line, word).on_line(2);
+        assert_eq!(Location::of(input, word), relative);
+    }
+
+    #[test]
+    fn snippet() {
+        let input = "move 1 from x to 3";
+        let location = Location::of(input, &input[12..13]);
+
+        assert_eq!(
+            "  |\n\
+             1 | move 1 from x to 3\n  \
+               |             ^",
+            location.snippet().to_string(),
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Random puzzle inputs, for stress-testing parsers and seeing how
+//! the solutions scale beyond the one input we were given.
+
+use crate::Solution;
+use rand::SeedableRng;
+
+pub use rand::{seq::SliceRandom, Rng};
+
+/// The random number generator handed to [`Generate::generate`].
+///
+/// ChaCha produces the same sequence on every platform and across
+/// versions of `rand`, so a seed always produces the same input.
+pub type GenerateRng = rand_chacha::ChaCha8Rng;
+
+/// A source of random, valid puzzle inputs.
+pub trait Generate: Solution {
+    /// Produces an input that [`Solution::parse`] accepts and that
+    /// both parts can solve with their real parameters.
+    ///
+    /// What `size` counts depends on the day; each implementation
+    /// documents it.
+    fn generate(rng: &mut GenerateRng, size: usize) -> String;
+}
+
+/// Produces the same input every time for the same seed and size.
+pub fn generate<G: Generate>(seed: u64, size: usize) -> String {
+    G::generate(&mut GenerateRng::seed_from_u64(seed), size)
+}
+
+/// Checks that the inputs for the first `seeds` seeds parse and that
+/// both parts solve them, as [`Generate::generate`] promises.
+pub fn solve_seeds<G: Generate>(seeds: u64, size: usize) -> Result<(), G::Error> {
+    for seed in 0..seeds {
+        let input = generate::<G>(seed, size);
+        let parsed = G::parse(&input)?;
+        G::part1(&parsed, G::PART1_PARAMS)?;
+        G::part2(&parsed, G::PART2_PARAMS)?;
+    }
+    Ok(())
+}
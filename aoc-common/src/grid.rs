@@ -0,0 +1,292 @@
+use snafu::prelude::*;
+use std::{
+    collections::BTreeMap,
+    ops::{Index, IndexMut},
+};
+
+use crate::{BoundingBox, Coordinate, LocateExt, Located, Point};
+
+/// A dense, rectangular grid with its top-left corner at `(0, 0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from text, one row per line and one cell per
+    /// character. Every line must be the same length.
+    pub fn parse<E>(
+        s: &str,
+        mut f: impl FnMut(Point<usize>, char) -> Result<T, E>,
+    ) -> Result<Self, Located<ParseGridError<E>>>
+    where
+        E: std::error::Error + 'static,
+    {
+        use parse_grid_error::*;
+
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (y, line) in s.lines().enumerate() {
+            let before = cells.len();
+
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let cell = f(Point::new(x, y), c)
+                    .context(CellSnafu { x, y })
+                    .locate(s, &line[i..][..c.len_utf8()])?;
+                cells.push(cell);
+            }
+
+            let actual = cells.len() - before;
+            let expected = *width.get_or_insert(actual);
+            if actual != expected {
+                return RaggedSnafu {
+                    y,
+                    expected,
+                    actual,
+                }
+                .fail()
+                .locate(s, line);
+            }
+
+            height += 1;
+        }
+
+        let width = width.unwrap_or(0);
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `None` when the grid has no cells.
+    pub fn bounds(&self) -> Option<BoundingBox<usize>> {
+        let max_x = self.width.checked_sub(1)?;
+        let max_y = self.height.checked_sub(1)?;
+        Some(BoundingBox {
+            min: Point::new(0, 0),
+            max: Point::new(max_x, max_y),
+        })
+    }
+
+    pub fn contains(&self, p: Point<usize>) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point<usize>) -> Option<&T> {
+        let i = self.index_of(p)?;
+        self.cells.get(i)
+    }
+
+    pub fn get_mut(&mut self, p: Point<usize>) -> Option<&mut T> {
+        let i = self.index_of(p)?;
+        self.cells.get_mut(i)
+    }
+
+    /// The orthogonally-adjacent points that are inside the grid.
+    pub fn neighbors(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        p.neighbors().filter(|&n| self.contains(n))
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        self.bounds().into_iter().flat_map(BoundingBox::points)
+    }
+
+    /// Every point and its value, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> + '_ {
+        self.points().zip(&self.cells)
+    }
+
+    fn index_of(&self, p: Point<usize>) -> Option<usize> {
+        self.contains(p).then(|| p.y * self.width + p.x)
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<usize>) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<usize>) -> &mut Self::Output {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseGridError<E>
+where
+    E: std::error::Error + 'static,
+{
+    #[snafu(display("The cell at ({x}, {y}) is invalid"))]
+    Cell { source: E, x: usize, y: usize },
+
+    #[snafu(display("Row {y} has {actual} cells but {expected} were expected"))]
+    Ragged {
+        y: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// A grid without fixed bounds that only stores the occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T, C = i64> {
+    cells: BTreeMap<Point<C>, T>,
+}
+
+impl<T, C> Default for SparseGrid<T, C> {
+    fn default() -> Self {
+        Self {
+            cells: Default::default(),
+        }
+    }
+}
+
+impl<T, C> SparseGrid<T, C>
+where
+    C: Coordinate,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the previous value, if any.
+    pub fn insert(&mut self, p: Point<C>, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn contains(&self, p: Point<C>) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point<C>) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    /// The bounds of the occupied cells; `None` when the grid is empty.
+    pub fn bounds(&self) -> Option<BoundingBox<C>> {
+        BoundingBox::from_points(self.cells.keys().copied())
+    }
+
+    /// The occupied cells, ordered by point.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<C>, &T)> + '_ {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.values()
+    }
+}
+
+impl<T, C> Extend<(Point<C>, T)> for SparseGrid<T, C>
+where
+    C: Ord,
+{
+    fn extend<I: IntoIterator<Item = (Point<C>, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter)
+    }
+}
+
+impl<T, C> FromIterator<(Point<C>, T)> for SparseGrid<T, C>
+where
+    C: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (Point<C>, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse("abc\ndef", |_, c| Ok::<_, Infallible>(c)).unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!('f', grid[Point::new(2, 1)]);
+        assert_eq!(None, grid.get(Point::new(3, 0)));
+
+        let neighbors = grid.neighbors(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbors, [Point::new(0, 1), Point::new(1, 0)]);
+
+        let values = grid.iter().map(|(_, &c)| c).collect::<String>();
+        assert_eq!("abcdef", values);
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let grid = Grid::parse("abc\nde", |_, c| Ok::<_, Infallible>(c));
+
+        let Err(Located { location, source }) = grid else {
+            panic!("The grid should have been rejected");
+        };
+
+        assert_eq!(2, location.line);
+        assert!(matches!(
+            source,
+            ParseGridError::Ragged {
+                y: 1,
+                expected: 3,
+                actual: 2,
+            }
+        ));
+    }
+
+    #[test]
+    fn sparse_bounds() {
+        let mut grid = SparseGrid::<(), i64>::new();
+        assert_eq!(None, grid.bounds());
+
+        grid.insert(Point::new(-2, 5), ());
+        grid.insert(Point::new(3, -1), ());
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(Point::new(-2, -1), bounds.min);
+        assert_eq!(Point::new(3, 5), bounds.max);
+    }
+}
@@ -0,0 +1,90 @@
+use snafu::prelude::*;
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, BufRead, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where the puzzle input should be read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl InputSource {
+    /// Interprets a command line argument, where `-` means standard
+    /// input. Without an argument, the `default` file is used.
+    pub fn from_arg(arg: Option<OsString>, default: impl AsRef<Path>) -> Self {
+        match arg {
+            Some(arg) if arg == "-" => Self::Stdin,
+            Some(arg) => Self::Path(arg.into()),
+            None => Self::Path(default.as_ref().to_owned()),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        use input_error::*;
+
+        match self {
+            Self::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s).context(StdinSnafu)?;
+                Ok(s)
+            }
+            Self::Path(path) => fs::read_to_string(path).context(ReadSnafu { path }),
+        }
+    }
+}
+
+/// The lines of `reader`, without their line endings, each with its
+/// 1-based line number for locating errors.
+///
+/// Only one line is held in memory at a time, so inputs much larger
+/// than memory can be processed.
+pub fn numbered_lines(reader: impl BufRead) -> impl Iterator<Item = (usize, io::Result<String>)> {
+    (1..).zip(reader.lines())
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum InputError {
+    #[snafu(display("Could not read the puzzle input from {}", path.display()))]
+    Read { source: io::Error, path: PathBuf },
+
+    #[snafu(display("Could not read the puzzle input from standard input"))]
+    Stdin { source: io::Error },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn source_from_arg() {
+        let default = "day/input";
+
+        assert_eq!(
+            InputSource::Path(default.into()),
+            InputSource::from_arg(None, default),
+        );
+        assert_eq!(
+            InputSource::Stdin,
+            InputSource::from_arg(Some("-".into()), default),
+        );
+        assert_eq!(
+            InputSource::Path("other".into()),
+            InputSource::from_arg(Some("other".into()), default),
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        let source = InputSource::Path("does/not/exist".into());
+
+        assert!(
+            matches!(source.read(), Err(InputError::Read { path, .. }) if path == Path::new("does/not/exist"))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Code shared between the individual days.
+
+pub mod answers;
+mod cli;
+pub mod diagnostic;
+pub mod generate;
+mod grid;
+pub mod input;
+pub mod output;
+mod point;
+mod solution;
+pub mod visualize;
+
+pub use answers::{AnswerCheck, AnswersError, InputHash, Verdict};
+pub use cli::{DayArgs, VisualDayArgs};
+pub use diagnostic::{LocateExt, Located, Location, WithinExt};
+pub use generate::Generate;
+pub use grid::{Grid, ParseGridError, SparseGrid};
+pub use input::InputError;
+pub use output::{Format, Output, Record};
+pub use point::{abs_diff, BoundingBox, Coordinate, Direction, Point};
+pub use solution::{Alternative, Solution};
+pub use visualize::{Frame, Palette, Recorder, Style, VisualizeArgs, VisualizeError};
@@ -0,0 +1,251 @@
+use crate::answers::{AnswerCheck, AnswersError, InputHash, Verdict};
+use serde::Serialize;
+use std::{error::Error, fmt, time::Instant};
+
+/// How answers are printed.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// The answer on standard output, anything else on standard error
+    #[default]
+    Text,
+
+    /// One JSON record per line on standard output
+    Json,
+}
+
+/// The outcome of one part of a day, for consumption by other
+/// programs.
+///
+/// Errors are stored as the chain of messages from the outermost
+/// error to the root cause. A record without a part describes a
+/// failure of the whole day, such as the input not being readable.
+///
+/// The allocation counts are only present when the runner counts
+/// allocations. The peak is of live heap memory, not resident memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub duration_ns: Option<u64>,
+    pub input_hash: Option<InputHash>,
+    pub error: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocated_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_heap_bytes: Option<u64>,
+}
+
+impl Record {
+    pub fn answer(
+        day: u8,
+        part: u8,
+        answer: &str,
+        duration: std::time::Duration,
+        input_hash: InputHash,
+    ) -> Self {
+        Self {
+            day,
+            part: Some(part),
+            answer: Some(answer.trim_end().to_owned()),
+            duration_ns: Some(duration.as_nanos().try_into().unwrap_or(u64::MAX)),
+            input_hash: Some(input_hash),
+            error: None,
+            allocations: None,
+            allocated_bytes: None,
+            peak_heap_bytes: None,
+        }
+    }
+
+    pub fn error(
+        day: u8,
+        part: Option<u8>,
+        input_hash: Option<InputHash>,
+        error: &(dyn Error + 'static),
+    ) -> Self {
+        Self {
+            day,
+            part,
+            answer: None,
+            duration_ns: None,
+            input_hash,
+            error: Some(error_chain(error)),
+            allocations: None,
+            allocated_bytes: None,
+            peak_heap_bytes: None,
+        }
+    }
+
+    /// A wrong answer is reported as an error, so that consumers only
+    /// need to look in one place.
+    pub fn with_verdict(mut self, verdict: &Verdict) -> Self {
+        if let Verdict::Wrong { .. } = verdict {
+            self.error = Some(vec![verdict.to_string()]);
+        }
+        self
+    }
+
+    pub fn with_allocations(mut self, allocations: u64, bytes: u64, peak_heap_bytes: u64) -> Self {
+        self.allocations = Some(allocations);
+        self.allocated_bytes = Some(bytes);
+        self.peak_heap_bytes = Some(peak_heap_bytes);
+        self
+    }
+
+    /// Prints the record as a single line of JSON on standard output.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        json.fmt(f)
+    }
+}
+
+/// The messages of an error and each of its sources.
+///
+/// Errors without their own message display their source's message
+/// instead; as with [`snafu::Report`], that redundant part is removed.
+pub fn error_chain(error: &(dyn Error + 'static)) -> Vec<String> {
+    let messages: Vec<_> = std::iter::successors(Some(error), |&e| e.source())
+        .map(ToString::to_string)
+        .collect();
+
+    let sources = messages.iter().skip(1).map(Some).chain([None]);
+
+    messages
+        .iter()
+        .zip(sources)
+        .map(|(message, source)| {
+            let trimmed = source
+                .and_then(|source| message.strip_suffix(source.as_str()))
+                .map(|m| m.trim_end_matches(": "))
+                .filter(|m| !m.is_empty());
+            trimmed.unwrap_or(message).to_owned()
+        })
+        .collect()
+}
+
+/// Solves the parts of a day, printing each answer in the requested
+/// format and checking it against the recorded answers.
+#[derive(Debug)]
+pub struct Output {
+    day: u8,
+    format: Format,
+    confirm: bool,
+    answers: AnswerCheck,
+}
+
+impl Output {
+    pub fn new(day: u8, format: Format, confirm: bool, answers: AnswerCheck) -> Self {
+        Self {
+            day,
+            format,
+            confirm,
+            answers,
+        }
+    }
+
+    /// Times `f` and reports its answer. An error is reported and
+    /// then returned.
+    pub fn solve<T, E>(&mut self, part: u8, f: impl FnOnce() -> Result<T, E>) -> Result<(), E>
+    where
+        T: fmt::Display,
+        E: Error + 'static,
+    {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+
+        match self.format {
+            Format::Text => self.answers.report(part, result?),
+
+            Format::Json => {
+                let hash = self.answers.hash();
+
+                let record = match &result {
+                    Ok(answer) => {
+                        let answer = answer.to_string();
+                        let verdict = self.answers.check(part, &answer);
+                        let record = Record::answer(self.day, part, &answer, duration, hash);
+
+                        if self.confirm {
+                            record
+                        } else {
+                            record.with_verdict(&verdict)
+                        }
+                    }
+                    Err(e) => Record::error(self.day, Some(part), Some(hash), e),
+                };
+
+                record.print();
+                result?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// See [`AnswerCheck::finish`].
+    pub fn finish(self) -> Result<(), AnswersError> {
+        self.answers.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Outer"))]
+    struct Outer {
+        source: Inner,
+    }
+
+    #[derive(Debug, Snafu)]
+    struct Middle {
+        source: Inner,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Inner"))]
+    struct Inner;
+
+    #[test]
+    fn records_as_json() {
+        let hash = InputHash::of("");
+        let duration = std::time::Duration::from_nanos(1234);
+
+        let record = Record::answer(5, 1, "CMZ\n", duration, hash);
+        assert_eq!(
+            r#"{"day":5,"part":1,"answer":"CMZ","duration_ns":1234,"input_hash":"cbf29ce484222325","error":null}"#,
+            record.to_string(),
+        );
+
+        let record = record.with_allocations(3, 300, 200);
+        assert_eq!(
+            r#"{"day":5,"part":1,"answer":"CMZ","duration_ns":1234,"input_hash":"cbf29ce484222325","error":null,"allocations":3,"allocated_bytes":300,"peak_heap_bytes":200}"#,
+            record.to_string(),
+        );
+
+        let error = InnerSnafu.fail::<()>().context(OuterSnafu).unwrap_err();
+        let record = Record::error(5, None, None, &error);
+        assert_eq!(
+            r#"{"day":5,"part":null,"answer":null,"duration_ns":null,"input_hash":null,"error":["Outer","Inner"]}"#,
+            record.to_string(),
+        );
+    }
+
+    #[test]
+    fn redundant_messages_are_removed() {
+        let error = InnerSnafu.fail::<()>().context(MiddleSnafu).unwrap_err();
+
+        assert_eq!(vec!["Middle", "Inner"], error_chain(&error));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+};
+
+/// The integer types that can be used as the components of a [`Point`].
+pub trait Coordinate: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// The absolute difference between two values, in the same type.
+pub fn abs_diff<T>(a: T, b: T) -> T
+where
+    T: Ord + Sub<Output = T>,
+{
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+/// The four orthogonal directions.
+///
+/// Follows screen conventions: `Up` decreases `y` and `Down` increases it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+}
+
+/// A location on a two-dimensional plane.
+///
+/// Points are ordered by `x`, then by `y`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Coordinate,
+{
+    /// The adjacent point in the given direction, if it can be represented.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let Self { x, y } = self;
+
+        Some(match direction {
+            Direction::Up => Self::new(x, y.checked_sub(T::ONE)?),
+            Direction::Down => Self::new(x, y.checked_add(T::ONE)?),
+            Direction::Left => Self::new(x.checked_sub(T::ONE)?, y),
+            Direction::Right => Self::new(x.checked_add(T::ONE)?, y),
+        })
+    }
+
+    /// The orthogonally-adjacent points that can be represented.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().flat_map(move |d| self.step(d))
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let x = self.x.checked_add(other.x)?;
+        let y = self.y.checked_add(other.y)?;
+        Some(Self::new(x, y))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let x = self.x.checked_sub(other.x)?;
+        let y = self.y.checked_sub(other.y)?;
+        Some(Self::new(x, y))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl<T> Add for Point<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Point<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> fmt::Display for Point<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The smallest axis-aligned rectangle containing a set of points.
+///
+/// Both corners are inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T> BoundingBox<T>
+where
+    T: Coordinate,
+{
+    /// Returns `None` when there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut this = Self {
+            min: first,
+            max: first,
+        };
+        this.extend(points);
+        Some(this)
+    }
+
+    /// Grows the box to include `point`.
+    pub fn include(&mut self, point: Point<T>) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// Every point inside the box, row by row.
+    pub fn points(self) -> impl Iterator<Item = Point<T>> {
+        let Self { min, max } = self;
+
+        inclusive(min.y, max.y)
+            .flat_map(move |y| inclusive(min.x, max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+impl<T> Extend<Point<T>> for BoundingBox<T>
+where
+    T: Coordinate,
+{
+    fn extend<I: IntoIterator<Item = Point<T>>>(&mut self, iter: I) {
+        for point in iter {
+            self.include(point);
+        }
+    }
+}
+
+// `RangeInclusive<T>` can only be iterated for the unstable `Step` types
+fn inclusive<T>(start: T, end: T) -> impl Iterator<Item = T>
+where
+    T: Coordinate,
+{
+    let first = (start <= end).then_some(start);
+    std::iter::successors(
+        first,
+        move |&v| if v < end { v.checked_add(T::ONE) } else { None },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn neighbors_skip_unrepresentable_points() {
+        let n = Point::<usize>::new(0, 0).neighbors().collect::<Vec<_>>();
+        assert_eq!(n, [Point::new(0, 1), Point::new(1, 0)]);
+
+        let n = Point::<i32>::new(0, 0).neighbors().count();
+        assert_eq!(n, 4);
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        let a = Point::<i64>::new(8, 7);
+        let b = Point::new(2, 10);
+        assert_eq!(9, a.manhattan_distance(b));
+        assert_eq!(9, b.manhattan_distance(a));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [
+            Point::<u32>::new(498, 4),
+            Point::new(498, 6),
+            Point::new(496, 6),
+        ];
+        let bounds = BoundingBox::from_points(points).unwrap();
+
+        assert_eq!(Point::new(496, 4), bounds.min);
+        assert_eq!(Point::new(498, 6), bounds.max);
+        assert_eq!((3, 3), (bounds.width(), bounds.height()));
+        assert_eq!(9, bounds.points().count());
+        assert!(bounds.points().all(|p| bounds.contains(p)));
+
+        assert_eq!(None, BoundingBox::<u32>::from_points([]));
+    }
+}
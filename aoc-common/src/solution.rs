@@ -0,0 +1,63 @@
+use std::{error::Error, fmt::Display};
+
+/// Another way of solving a part, with the name it is reported by.
+pub type Alternative<S, P, T> = (
+    &'static str,
+    for<'a> fn(&<S as Solution>::Input<'a>, P) -> Result<T, <S as Solution>::Error>,
+);
+
+/// A puzzle solution that tooling can drive without knowing the
+/// details of the day.
+///
+/// The input is parsed once and then shared by both parts. Some parts
+/// need extra values that are not part of the input (e.g. how many
+/// elves to consider); those are passed as the part's parameters, and
+/// the values that go with the real puzzle input are available as
+/// [`Self::PART1_PARAMS`] and [`Self::PART2_PARAMS`].
+pub trait Solution {
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// The parsed puzzle input, which may borrow from the text.
+    type Input<'a>;
+
+    type Error: Error + 'static;
+
+    type Part1Params;
+    type Part1: Display;
+
+    type Part2Params;
+    type Part2: Display;
+
+    const PART1_PARAMS: Self::Part1Params;
+    const PART2_PARAMS: Self::Part2Params;
+
+    /// The parameters that go with the example input from the puzzle
+    /// description, when they differ from the real ones.
+    const EXAMPLE_PART1_PARAMS: Self::Part1Params = Self::PART1_PARAMS;
+    const EXAMPLE_PART2_PARAMS: Self::Part2Params = Self::PART2_PARAMS;
+
+    fn parse(s: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+    fn part1(
+        input: &Self::Input<'_>,
+        params: Self::Part1Params,
+    ) -> Result<Self::Part1, Self::Error>;
+
+    fn part2(
+        input: &Self::Input<'_>,
+        params: Self::Part2Params,
+    ) -> Result<Self::Part2, Self::Error>;
+
+    /// Other ways of solving part 1, which should always agree with
+    /// [`Self::part1`]. `aoc verify` checks that they do.
+    fn part1_alternatives() -> Vec<Alternative<Self, Self::Part1Params, Self::Part1>> {
+        Vec::new()
+    }
+
+    /// Other ways of solving part 2, which should always agree with
+    /// [`Self::part2`].
+    fn part2_alternatives() -> Vec<Alternative<Self, Self::Part2Params, Self::Part2>> {
+        Vec::new()
+    }
+}
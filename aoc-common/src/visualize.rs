@@ -0,0 +1,613 @@
+//! Drawing the state of grid-based puzzles as text, images or
+//! animations.
+//!
+//! A day describes what is at each point as a [`Frame`] of its own
+//! cell type and supplies a [`Palette`] saying how each kind of cell
+//! looks. A [`Recorder`] collects frames while the puzzle is solved
+//! and writes them out once it is finished, so that every frame can
+//! share one bounding box.
+
+use crate::{BoundingBox, Grid, Point, SparseGrid};
+use image::{
+    codecs::{
+        gif::{GifEncoder, Repeat},
+        png::PngEncoder,
+        pnm::{PnmEncoder, PnmSubtype, SampleEncoding},
+    },
+    Delay, ImageEncoder, Rgb, RgbImage, RgbaImage,
+};
+use snafu::prelude::*;
+use std::{
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+/// The contents of the drawn points; anything absent is background.
+pub type Frame<K> = SparseGrid<K, i64>;
+
+/// Builds a frame from a dense grid, leaving out the cells that `f`
+/// returns `None` for.
+pub fn frame_from_grid<T, K>(
+    grid: &Grid<T>,
+    mut f: impl FnMut(Point<usize>, &T) -> Option<K>,
+) -> Frame<K> {
+    grid.iter()
+        .flat_map(|(p, v)| {
+            let cell = f(p, v)?;
+            let x = p.x.try_into().ok()?;
+            let y = p.y.try_into().ok()?;
+            Some((Point::new(x, y), cell))
+        })
+        .collect()
+}
+
+/// An RGB color, written as `rrggbb` hex digits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Color(pub [u8; 3]);
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        ensure!(
+            digits.len() == 6 && digits.is_ascii(),
+            ParseColorSnafu { color: s }
+        );
+
+        let channel = |i| u8::from_str_radix(&digits[i..][..2], 16);
+        match (channel(0), channel(2), channel(4)) {
+            (Ok(r), Ok(g), Ok(b)) => Ok(Self([r, g, b])),
+            _ => ParseColorSnafu { color: s }.fail(),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("{color:?} is not a color; expected six hex digits like `ff8800`"))]
+pub struct ParseColorError {
+    color: String,
+}
+
+/// How one kind of cell is drawn: a character in text and a color in
+/// images.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Style {
+    pub const fn new(glyph: char, color: [u8; 3]) -> Self {
+        Self {
+            glyph,
+            color: Color(color),
+        }
+    }
+}
+
+/// The [`Style`] of each kind of cell, plus the background.
+#[derive(Debug, Clone)]
+pub struct Palette<K> {
+    background: Style,
+    cells: Vec<(K, Style)>,
+}
+
+impl<K> Palette<K>
+where
+    K: PartialEq,
+{
+    pub fn new(background: Style) -> Self {
+        Self {
+            background,
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, cell: K, style: Style) -> Self {
+        self.cells.push((cell, style));
+        self
+    }
+
+    /// Replaces the colors in order, starting with the background and
+    /// continuing in the order the cells were added. Any styles beyond
+    /// the end of `colors` are left alone.
+    pub fn recolor(&mut self, colors: &[Color]) {
+        let styles = std::iter::once(&mut self.background)
+            .chain(self.cells.iter_mut().map(|(_, style)| style));
+
+        for (style, &color) in styles.zip(colors) {
+            style.color = color;
+        }
+    }
+
+    /// Cells without a style of their own use the background.
+    pub fn style(&self, cell: Option<&K>) -> Style {
+        cell.and_then(|cell| self.cells.iter().find(|(k, _)| k == cell))
+            .map_or(self.background, |&(_, style)| style)
+    }
+}
+
+/// Draws the part of `frame` inside `bounds` as text, one line per
+/// row.
+pub fn ascii<K>(frame: &Frame<K>, bounds: BoundingBox<i64>, palette: &Palette<K>) -> String
+where
+    K: PartialEq,
+{
+    let mut s = String::new();
+
+    for p in bounds.points() {
+        s.push(palette.style(frame.get(p)).glyph);
+        if p.x == bounds.max.x {
+            s.push('\n');
+        }
+    }
+
+    s
+}
+
+/// Draws the part of `frame` inside `bounds` as an image, with each
+/// cell `scale` pixels square.
+pub fn image<K>(
+    frame: &Frame<K>,
+    bounds: BoundingBox<i64>,
+    palette: &Palette<K>,
+    scale: NonZeroU32,
+) -> Result<RgbImage, VisualizeError>
+where
+    K: PartialEq,
+{
+    let scale = scale.get();
+    let size = |cells: i64| {
+        u32::try_from(cells)
+            .ok()
+            .and_then(|c| c.checked_mul(scale))
+            .filter(|&px| px <= MAX_IMAGE_SIDE)
+    };
+    let (width, height) = match (size(bounds.width()), size(bounds.height())) {
+        (Some(w), Some(h)) => (w, h),
+        _ => {
+            return TooLargeSnafu {
+                width: bounds.width(),
+                height: bounds.height(),
+            }
+            .fail()
+        }
+    };
+
+    let Color(background) = palette.background.color;
+    let mut image = RgbImage::from_pixel(width, height, Rgb(background));
+
+    for (p, cell) in frame.iter().filter(|&(p, _)| bounds.contains(p)) {
+        let Color(color) = palette.style(Some(cell)).color;
+        // Both fit in a `u32` as the whole image does
+        let x0 = (p.x - bounds.min.x) as u32 * scale;
+        let y0 = (p.y - bounds.min.y) as u32 * scale;
+
+        for y in y0..y0 + scale {
+            for x in x0..x0 + scale {
+                image.put_pixel(x, y, Rgb(color));
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+/// Larger images are almost certainly a mistake.
+const MAX_IMAGE_SIDE: u32 = 16_384;
+
+/// How the drawing is written out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum FileFormat {
+    /// Text, with a blank line between frames
+    Ascii,
+
+    /// A binary PPM image per frame
+    Ppm,
+
+    /// A PNG image per frame
+    Png,
+
+    /// One animated GIF
+    Gif,
+}
+
+impl FileFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Some(match &*extension {
+            "txt" => Self::Ascii,
+            "ppm" => Self::Ppm,
+            "png" => Self::Png,
+            "gif" => Self::Gif,
+            _ => return None,
+        })
+    }
+}
+
+/// The command line options of the days that can draw their working.
+#[derive(Debug, Clone, clap::Args)]
+pub struct VisualizeArgs {
+    /// Draw the puzzle's working to this file
+    #[arg(long, value_name = "PATH")]
+    pub visualize: Option<PathBuf>,
+
+    /// The format of the drawing, if not the one implied by the
+    /// file's extension
+    #[arg(long, value_enum, requires = "visualize")]
+    pub visualize_format: Option<FileFormat>,
+
+    /// Keep only every Nth frame of an animation
+    #[arg(long, default_value = "1")]
+    pub frame_every: NonZeroUsize,
+
+    /// Time between the frames of an animation, in milliseconds
+    #[arg(long, default_value_t = 50)]
+    pub frame_delay: u64,
+
+    /// The width and height of a cell in an image, in pixels
+    #[arg(long, default_value = "4")]
+    pub scale: NonZeroU32,
+
+    /// Comma-separated colors to use instead of the day's own, starting
+    /// with the background
+    #[arg(long, value_delimiter = ',')]
+    pub palette: Vec<Color>,
+}
+
+impl VisualizeArgs {
+    /// A recorder that does nothing unless a drawing was requested.
+    pub fn recorder<K>(&self, mut palette: Palette<K>) -> Result<Recorder<K>, VisualizeError>
+    where
+        K: PartialEq,
+    {
+        let Some(path) = &self.visualize else {
+            return Ok(Recorder::disabled(palette));
+        };
+
+        let format = match self.visualize_format {
+            Some(format) => format,
+            None => FileFormat::from_path(path).context(UnknownFormatSnafu { path })?,
+        };
+
+        palette.recolor(&self.palette);
+
+        let target = Target {
+            path: path.clone(),
+            format,
+            every: self.frame_every,
+            delay: Duration::from_millis(self.frame_delay),
+            scale: self.scale,
+        };
+
+        Ok(Recorder {
+            target: Some(target),
+            palette,
+            frames: Vec::new(),
+            offered: 0,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Target {
+    path: PathBuf,
+    format: FileFormat,
+    every: NonZeroUsize,
+    delay: Duration,
+    scale: NonZeroU32,
+}
+
+/// Collects the frames of a drawing and writes them out.
+///
+/// Frames are kept in memory until [`Recorder::finish`]; use
+/// [`VisualizeArgs::frame_every`] to thin out long animations.
+#[derive(Debug)]
+pub struct Recorder<K> {
+    target: Option<Target>,
+    palette: Palette<K>,
+    frames: Vec<Frame<K>>,
+    offered: usize,
+}
+
+impl<K> Recorder<K>
+where
+    K: PartialEq,
+{
+    pub fn disabled(palette: Palette<K>) -> Self {
+        Self {
+            target: None,
+            palette,
+            frames: Vec::new(),
+            offered: 0,
+        }
+    }
+
+    /// Offers the next frame of the drawing. `frame` is only called
+    /// when the frame is kept.
+    pub fn record(&mut self, frame: impl FnOnce() -> Frame<K>) {
+        let Some(target) = &self.target else { return };
+
+        if self.offered % target.every == 0 {
+            self.frames.push(frame());
+        }
+        self.offered += 1;
+    }
+
+    /// Writes the frames to the requested file, sized to fit every
+    /// cell of every frame.
+    pub fn finish(self) -> Result<(), VisualizeError> {
+        let Some(target) = self.target else {
+            return Ok(());
+        };
+        let Self {
+            palette, frames, ..
+        } = self;
+
+        let bounds = frames.iter().filter_map(SparseGrid::bounds);
+        let bounds = bounds
+            .reduce(|mut a, b| {
+                a.extend([b.min, b.max]);
+                a
+            })
+            .context(NothingToDrawSnafu)?;
+
+        let Target {
+            path,
+            format,
+            delay,
+            scale,
+            ..
+        } = target;
+
+        match format {
+            FileFormat::Ascii => {
+                let mut w = create(&path)?;
+                for (i, frame) in frames.iter().enumerate() {
+                    if i != 0 {
+                        writeln!(w).context(WriteSnafu { path: &path })?;
+                    }
+                    w.write_all(ascii(frame, bounds, &palette).as_bytes())
+                        .context(WriteSnafu { path: &path })?;
+                }
+                w.flush().context(WriteSnafu { path: &path })?;
+            }
+
+            FileFormat::Ppm | FileFormat::Png => {
+                let single = frames.len() == 1;
+
+                for (i, frame) in frames.iter().enumerate() {
+                    let path = if single {
+                        path.clone()
+                    } else {
+                        numbered(&path, i)
+                    };
+                    let image = image(frame, bounds, &palette, scale)?;
+                    let mut w = create(&path)?;
+
+                    let (width, height) = image.dimensions();
+                    let color = image::ColorType::Rgb8;
+                    let encoded = if format == FileFormat::Ppm {
+                        PnmEncoder::new(&mut w)
+                            .with_subtype(PnmSubtype::Pixmap(SampleEncoding::Binary))
+                            .write_image(&image, width, height, color)
+                    } else {
+                        PngEncoder::new(&mut w).write_image(&image, width, height, color)
+                    };
+                    encoded.context(EncodeSnafu { path: &path })?;
+                    w.flush().context(WriteSnafu { path: &path })?;
+                }
+            }
+
+            FileFormat::Gif => {
+                let w = create(&path)?;
+                let mut encoder = GifEncoder::new(w);
+                encoder
+                    .set_repeat(Repeat::Infinite)
+                    .context(EncodeSnafu { path: &path })?;
+
+                for frame in &frames {
+                    let image = image(frame, bounds, &palette, scale)?;
+                    let image = RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+                        let Rgb([r, g, b]) = *image.get_pixel(x, y);
+                        image::Rgba([r, g, b, u8::MAX])
+                    });
+                    let delay = Delay::from_saturating_duration(delay);
+                    let frame = image::Frame::from_parts(image, 0, 0, delay);
+                    encoder
+                        .encode_frame(frame)
+                        .context(EncodeSnafu { path: &path })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, VisualizeError> {
+    File::create(path)
+        .map(BufWriter::new)
+        .context(CreateSnafu { path })
+}
+
+/// `cave.png` becomes `cave-00042.png`.
+fn numbered(path: &Path, i: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("{stem}-{i:05}");
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+#[derive(Debug, Snafu)]
+pub enum VisualizeError {
+    #[snafu(display(
+        "Could not tell the drawing format from {}; use a .txt, .ppm, .png or .gif extension",
+        path.display(),
+    ))]
+    UnknownFormat { path: PathBuf },
+
+    #[snafu(display("Nothing was drawn"))]
+    NothingToDraw,
+
+    #[snafu(display("A drawing of {width}x{height} cells is too large"))]
+    TooLarge { width: i64, height: i64 },
+
+    #[snafu(display("Could not create {}", path.display()))]
+    Create {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not write {}", path.display()))]
+    Write {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not encode {}", path.display()))]
+    Encode {
+        source: image::ImageError,
+        path: PathBuf,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Cell {
+        Wall,
+        Sand,
+    }
+
+    fn palette() -> Palette<Cell> {
+        Palette::new(Style::new('.', [0, 0, 0]))
+            .with(Cell::Wall, Style::new('#', [0x80, 0x80, 0x80]))
+            .with(Cell::Sand, Style::new('o', [0xff, 0xcc, 0x00]))
+    }
+
+    fn frame() -> Frame<Cell> {
+        [
+            (Point::new(-1, 0), Cell::Sand),
+            (Point::new(1, 1), Cell::Wall),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn ascii_fills_the_bounds() {
+        let frame = frame();
+        let bounds = frame.bounds().unwrap();
+
+        assert_eq!("o..\n..#\n", ascii(&frame, bounds, &palette()));
+    }
+
+    #[test]
+    fn image_scales_cells() {
+        let frame = frame();
+        let bounds = frame.bounds().unwrap();
+        let scale = NonZeroU32::new(2).unwrap();
+
+        let image = image(&frame, bounds, &palette(), scale).unwrap();
+
+        assert_eq!((6, 4), image.dimensions());
+        assert_eq!(Rgb([0xff, 0xcc, 0x00]), *image.get_pixel(1, 1));
+        assert_eq!(Rgb([0, 0, 0]), *image.get_pixel(2, 1));
+        assert_eq!(Rgb([0x80, 0x80, 0x80]), *image.get_pixel(5, 3));
+    }
+
+    #[test]
+    fn recolor_starts_with_the_background() {
+        let mut palette = palette();
+        palette.recolor(&["ffffff".parse().unwrap(), "#010203".parse().unwrap()]);
+
+        assert_eq!(Color([0xff, 0xff, 0xff]), palette.style(None).color);
+        assert_eq!(Color([1, 2, 3]), palette.style(Some(&Cell::Wall)).color);
+        assert_eq!(
+            Color([0xff, 0xcc, 0x00]),
+            palette.style(Some(&Cell::Sand)).color
+        );
+    }
+
+    #[test]
+    fn colors_round_trip() {
+        let color: Color = "ff8800".parse().unwrap();
+        assert_eq!(Color([0xff, 0x88, 0x00]), color);
+        assert_eq!("ff8800", color.to_string());
+
+        assert!("ff880".parse::<Color>().is_err());
+        assert!("gg8800".parse::<Color>().is_err());
+        assert!("ff88é".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn frames_are_numbered() {
+        assert_eq!(
+            PathBuf::from("/tmp/cave-00042.png"),
+            numbered(Path::new("/tmp/cave.png"), 42),
+        );
+    }
+
+    #[test]
+    fn animations_share_one_bounding_box() {
+        let dir = std::env::temp_dir().join(format!("aoc-visualize-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sand.txt");
+
+        let args = VisualizeArgs {
+            visualize: Some(path.clone()),
+            visualize_format: None,
+            frame_every: NonZeroUsize::new(2).unwrap(),
+            frame_delay: 50,
+            scale: NonZeroU32::new(1).unwrap(),
+            palette: vec![],
+        };
+        let mut recorder = args.recorder(palette()).unwrap();
+
+        recorder.record(|| [(Point::new(0, 0), Cell::Sand)].into_iter().collect());
+        recorder.record(|| unreachable!("Only every other frame is kept"));
+        recorder.record(|| [(Point::new(2, 0), Cell::Wall)].into_iter().collect());
+        recorder.finish().unwrap();
+
+        let drawn = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!("o..\n\n..#\n", drawn);
+    }
+
+    #[test]
+    fn unknown_extensions_are_rejected() {
+        let args = VisualizeArgs {
+            visualize: Some("cave.bmp".into()),
+            visualize_format: None,
+            frame_every: NonZeroUsize::new(1).unwrap(),
+            frame_delay: 50,
+            scale: NonZeroU32::new(1).unwrap(),
+            palette: vec![],
+        };
+
+        assert!(matches!(
+            args.recorder(palette()),
+            Err(VisualizeError::UnknownFormat { .. })
+        ));
+    }
+}
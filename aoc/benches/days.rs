@@ -0,0 +1,105 @@
+//! Benchmarks parsing and each part of every day, against the real
+//! puzzle input, the example from the puzzle description, and
+//! generated inputs of increasing size.
+//!
+//! Benchmarks are named
+//! `day-NN/{parse,part1,part2}/{input,example,generated-SIZE}`, so a
+//! single day, step, or kind of input can be selected with a filter:
+//!
+//! ```text
+//! cargo bench -p aoc --bench days -- day-07/
+//! cargo bench -p aoc --bench days -- generated
+//! ```
+//!
+//! For output that can be diffed across commits, use
+//! `--output-format bencher`, which prints one line per benchmark.
+//! Criterion also saves its estimates as JSON under
+//! `target/criterion`; `--save-baseline` and `--baseline` compare
+//! against a previous run.
+
+use aoc_common::{generate::generate, Generate};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::{fs, path::Path};
+
+/// The seed for all generated inputs, so that runs can be compared.
+const SEED: u64 = 0;
+
+fn bench_day<S: Generate>(c: &mut Criterion, dir: &str, sizes: &[usize]) {
+    let day_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(dir);
+
+    let mut group = c.benchmark_group(dir);
+    // Some days take the better part of a second to solve
+    group.sample_size(10);
+
+    let mut inputs = vec![];
+
+    for (name, file, example) in [("input", "input", false), ("example", "input.test", true)] {
+        // Not every day has its example saved as a file
+        if let Ok(input) = fs::read_to_string(day_dir.join(file)) {
+            inputs.push((name.to_owned(), input, example));
+        }
+    }
+
+    for &size in sizes {
+        inputs.push((
+            format!("generated-{size}"),
+            generate::<S>(SEED, size),
+            false,
+        ));
+    }
+
+    for (name, input, example) in inputs {
+        // Parameters aren't required to be `Clone`, so each iteration
+        // asks for a fresh copy of the constant
+        let part1_params: fn() -> S::Part1Params = if example {
+            || S::EXAMPLE_PART1_PARAMS
+        } else {
+            || S::PART1_PARAMS
+        };
+        let part2_params: fn() -> S::Part2Params = if example {
+            || S::EXAMPLE_PART2_PARAMS
+        } else {
+            || S::PART2_PARAMS
+        };
+
+        let parsed = match S::parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => panic!("Could not parse the {name} for {dir}: {e}"),
+        };
+
+        group.bench_function(format!("parse/{name}"), |b| {
+            b.iter(|| S::parse(black_box(&input)))
+        });
+        group.bench_function(format!("part1/{name}"), |b| {
+            b.iter(|| S::part1(black_box(&parsed), part1_params()))
+        });
+        group.bench_function(format!("part2/{name}"), |b| {
+            b.iter(|| S::part2(black_box(&parsed), part2_params()))
+        });
+    }
+
+    group.finish();
+}
+
+// Generated sizes are limited to what each day solves in well under a
+// second; some parts don't scale linearly.
+fn days(c: &mut Criterion) {
+    bench_day::<day_01::Day01>(c, "day-01", &[10, 100, 1000]);
+    bench_day::<day_02::Day02>(c, "day-02", &[10, 100, 1000]);
+    bench_day::<day_03::Day03>(c, "day-03", &[10, 100, 1000]);
+    bench_day::<day_04::Day04>(c, "day-04", &[10, 100, 1000]);
+    bench_day::<day_05::Day05>(c, "day-05", &[10, 100, 1000]);
+    bench_day::<day_06::Day06>(c, "day-06", &[10, 100, 1000]);
+    bench_day::<day_07::Day07>(c, "day-07", &[10, 100, 1000]);
+    bench_day::<day_08::Day08>(c, "day-08", &[10, 100, 300]);
+    bench_day::<day_09::Day09>(c, "day-09", &[10, 100, 1000]);
+    bench_day::<day_10::Day10>(c, "day-10", &[10, 100, 1000]);
+    bench_day::<day_11::Day11>(c, "day-11", &[10, 100, 1000]);
+    bench_day::<day_12::Day12>(c, "day-12", &[10, 30]);
+    bench_day::<day_13::Day13>(c, "day-13", &[10, 100, 1000]);
+    bench_day::<day_14::Day14>(c, "day-14", &[10, 100]);
+    bench_day::<day_15::Day15>(c, "day-15", &[10]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,243 @@
+//! Counting the memory each part allocates.
+//!
+//! Only heap allocations made through the global allocator are seen,
+//! so the peak is of live heap memory, not of the process's resident
+//! memory.
+//!
+//! The counting allocator is only installed when the runner is built
+//! with the `count-allocations` feature:
+//!
+//! ```text
+//! cargo run --release --features count-allocations -p aoc -- run all
+//! ```
+
+// Without the feature, the allocator is only used by the tests
+#![cfg_attr(not(feature = "count-allocations"), allow(dead_code))]
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting<std::alloc::System> = Counting::new(std::alloc::System);
+
+/// Runs `f`, reporting what it allocated when the counting allocator
+/// is installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    #[cfg(feature = "count-allocations")]
+    {
+        let (value, usage) = ALLOCATOR.measure(f);
+        (value, Some(usage))
+    }
+
+    #[cfg(not(feature = "count-allocations"))]
+    {
+        (f(), None)
+    }
+}
+
+/// What was allocated while measuring.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Usage {
+    /// Each reallocation counts as another allocation.
+    pub allocations: u64,
+    /// The total size of every allocation, including memory that was
+    /// freed again.
+    pub bytes: u64,
+    /// The most heap memory that was in use at once, beyond what was
+    /// already in use when measuring started.
+    pub peak_heap_bytes: u64,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak heap",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak_heap_bytes),
+        )
+    }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut size = self.0 as f64 / 1024.0;
+        let mut units = UNITS.iter();
+        let mut unit = units.next().expect("There is always a unit");
+        for next in units {
+            if size < 1024.0 {
+                break;
+            }
+            size /= 1024.0;
+            unit = next;
+        }
+
+        write!(f, "{size:.1} {unit}")
+    }
+}
+
+/// Wraps another allocator, keeping count of what passes through it.
+pub struct Counting<A> {
+    inner: A,
+    allocations: AtomicU64,
+    bytes: AtomicU64,
+    live: AtomicU64,
+    peak: AtomicU64,
+}
+
+impl<A> Counting<A> {
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            allocations: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            live: AtomicU64::new(0),
+            peak: AtomicU64::new(0),
+        }
+    }
+
+    /// Runs `f`, reporting what it allocated. Allocations made by
+    /// other threads at the same time are counted too.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, Usage) {
+        let allocations = self.allocations.load(Ordering::Relaxed);
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        let live = self.live.load(Ordering::Relaxed);
+        self.peak.store(live, Ordering::Relaxed);
+
+        let value = f();
+
+        let usage = Usage {
+            allocations: self.allocations.load(Ordering::Relaxed) - allocations,
+            bytes: self.bytes.load(Ordering::Relaxed) - bytes,
+            peak_heap_bytes: self.peak.load(Ordering::Relaxed).saturating_sub(live),
+        };
+
+        (value, usage)
+    }
+
+    fn allocated(&self, size: usize) {
+        let size = size as u64;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        let live = self.live.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(&self, size: usize) {
+        self.live.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: Every call is passed on to the inner allocator unchanged.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // The old and new memory may both be in use while copying
+            self.allocated(new_size);
+            self.freed(layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::alloc::System;
+
+    #[test]
+    fn counts_allocations_and_peak() {
+        let counting = Counting::new(System);
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(1000, 8).unwrap();
+
+        let ((), usage) = counting.measure(|| unsafe {
+            let a = counting.alloc(small);
+            let b = counting.alloc_zeroed(large);
+            counting.dealloc(b, large);
+            let a = counting.realloc(a, small, 200);
+            counting.dealloc(a, Layout::from_size_align(200, 8).unwrap());
+        });
+
+        assert_eq!(
+            Usage {
+                allocations: 3,
+                bytes: 1300,
+                peak_heap_bytes: 1100,
+            },
+            usage,
+        );
+    }
+
+    #[test]
+    fn peak_is_relative_to_the_start() {
+        let counting = Counting::new(System);
+        let layout = Layout::from_size_align(500, 8).unwrap();
+
+        let kept = unsafe { counting.alloc(layout) };
+        let ((), usage) = counting.measure(|| unsafe {
+            counting.dealloc(counting.alloc(layout), layout);
+        });
+        unsafe { counting.dealloc(kept, layout) };
+
+        assert_eq!(1, usage.allocations);
+        assert_eq!(500, usage.peak_heap_bytes);
+    }
+
+    #[test]
+    fn reallocation_holds_both_buffers() {
+        let counting = Counting::new(System);
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(300, 8).unwrap();
+
+        let ((), usage) = counting.measure(|| unsafe {
+            let a = counting.alloc(small);
+            let a = counting.realloc(a, small, large.size());
+            counting.dealloc(a, large);
+        });
+
+        assert_eq!(400, usage.peak_heap_bytes);
+    }
+
+    #[test]
+    fn sizes_are_readable() {
+        assert_eq!("12 B", Bytes(12).to_string());
+        assert_eq!("1.5 KiB", Bytes(1536).to_string());
+        assert_eq!("3.0 MiB", Bytes(3 * 1024 * 1024).to_string());
+    }
+}
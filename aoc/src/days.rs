@@ -0,0 +1,114 @@
+use aoc_common::{generate, Alternative, Solution};
+use std::{error::Error, path::PathBuf};
+
+pub type PartResult = Result<String, Box<dyn Error>>;
+pub type ParseFn = for<'a> fn(&'a str) -> Result<Box<dyn Parsed + 'a>, Box<dyn Error>>;
+pub type GenerateFn = fn(u64, usize) -> String;
+pub type VerifyFn = fn(&str) -> Result<[PartAnswers; 2], Box<dyn Error>>;
+
+pub struct Day {
+    pub number: u8,
+    pub input_path: &'static str,
+    pub answers_path: &'static str,
+    pub parse: ParseFn,
+    pub generate: GenerateFn,
+    pub verify: VerifyFn,
+}
+
+/// What each implementation of a part answered, by name.
+#[derive(Debug)]
+pub struct PartAnswers(pub Vec<(&'static str, Result<String, String>)>);
+
+impl PartAnswers {
+    /// Implementations agree when they all find the same answer or
+    /// all fail, however they describe the failure.
+    pub fn agree(&self) -> bool {
+        let mut answers = self.0.iter().map(|(_, a)| a.as_ref().ok());
+        match answers.next() {
+            Some(first) => answers.all(|a| a == first),
+            None => true,
+        }
+    }
+}
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/// Where the crate for a day lives, whether or not it exists yet.
+pub fn directory(number: u8) -> PathBuf {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+    [root, &format!("day-{number:02}")].iter().collect()
+}
+
+/// A day's input, parsed once so that both parts share it.
+pub trait Parsed {
+    /// `part` is 1 or 2.
+    fn part(&self, part: u8) -> PartResult;
+}
+
+struct ParsedInput<'a, S: Solution>(S::Input<'a>);
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn part(&self, part: u8) -> PartResult {
+        let answer = match part {
+            1 => S::part1(&self.0, S::PART1_PARAMS)?.to_string(),
+            _ => S::part2(&self.0, S::PART2_PARAMS)?.to_string(),
+        };
+        Ok(answer)
+    }
+}
+
+fn parse<S: Solution + 'static>(s: &str) -> Result<Box<dyn Parsed + '_>, Box<dyn Error>> {
+    Ok(Box::new(ParsedInput::<S>(S::parse(s)?)))
+}
+
+fn verify<S: Solution>(s: &str) -> Result<[PartAnswers; 2], Box<dyn Error>> {
+    let input = S::parse(s)?;
+
+    let part1: Alternative<S, _, _> = ("part1", S::part1);
+    let part1 = std::iter::once(part1).chain(S::part1_alternatives());
+    let part1 = part1.map(|(name, f)| (name, answer(f(&input, S::PART1_PARAMS))));
+
+    let part2: Alternative<S, _, _> = ("part2", S::part2);
+    let part2 = std::iter::once(part2).chain(S::part2_alternatives());
+    let part2 = part2.map(|(name, f)| (name, answer(f(&input, S::PART2_PARAMS))));
+
+    Ok([PartAnswers(part1.collect()), PartAnswers(part2.collect())])
+}
+
+fn answer<T: std::fmt::Display, E: Error>(r: Result<T, E>) -> Result<String, String> {
+    r.map(|a| a.to_string())
+        .map_err(|e| snafu::Report::from_error(e).to_string())
+}
+
+macro_rules! day {
+    ($solution:ty, $dir:literal) => {
+        Day {
+            number: <$solution as Solution>::DAY,
+            input_path: concat!(env!("CARGO_MANIFEST_DIR"), "/../", $dir, "/input"),
+            answers_path: concat!(env!("CARGO_MANIFEST_DIR"), "/../", $dir, "/answers"),
+            parse: parse::<$solution>,
+            generate: generate::generate::<$solution>,
+            verify: verify::<$solution>,
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(day_01::Day01, "day-01"),
+    day!(day_02::Day02, "day-02"),
+    day!(day_03::Day03, "day-03"),
+    day!(day_04::Day04, "day-04"),
+    day!(day_05::Day05, "day-05"),
+    day!(day_06::Day06, "day-06"),
+    day!(day_07::Day07, "day-07"),
+    day!(day_08::Day08, "day-08"),
+    day!(day_09::Day09, "day-09"),
+    day!(day_10::Day10, "day-10"),
+    day!(day_11::Day11, "day-11"),
+    day!(day_12::Day12, "day-12"),
+    day!(day_13::Day13, "day-13"),
+    day!(day_14::Day14, "day-14"),
+    day!(day_15::Day15, "day-15"),
+];
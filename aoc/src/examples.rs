@@ -0,0 +1,334 @@
+use snafu::prelude::*;
+use std::fmt::Write;
+
+/// The examples given in a puzzle's description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Examples {
+    pub input: String,
+    /// Part 2's example, when it is not the same as part 1's.
+    pub input2: Option<String>,
+    /// The answer for the example, for each part that has been
+    /// described.
+    pub answers: Vec<Option<String>>,
+}
+
+impl Examples {
+    /// Reads a puzzle page as saved by a browser.
+    ///
+    /// Each part is described in its own `<article>`. Part 1's
+    /// example input is the first `<pre><code>` block of its article,
+    /// and a part's answer is the last emphasized code there. Part 2
+    /// does not always give a new example and its blocks are often
+    /// drawings of the working, so only a block that the text
+    /// introduces as an example is taken.
+    pub fn from_html(html: &str) -> Result<Self, ExamplesError> {
+        use examples_error::*;
+
+        let articles = contents(html, "<article", "</article>").collect::<Vec<_>>();
+        ensure!(!articles.is_empty(), DescriptionMissingSnafu);
+
+        let input = contents(articles[0], "<pre><code>", "</code></pre>")
+            .next()
+            .map(text)
+            .context(ExampleMissingSnafu)?;
+        let input2 = articles
+            .get(1)
+            .and_then(|article| introduced_example(article))
+            .filter(|i| *i != input);
+
+        let answers = articles
+            .iter()
+            .map(|article| {
+                let em_code = contents(article, "<em><code>", "</code></em>");
+                let code_em = contents(article, "<code><em>", "</em></code>");
+                let last = em_code.chain(code_em).max_by_key(|s| s.as_ptr());
+                last.map(text)
+            })
+            .collect();
+
+        Ok(Self {
+            input,
+            input2,
+            answers,
+        })
+    }
+
+    /// Tests of the examples against the answers, in the shape of the
+    /// test module that `aoc new` creates. A part whose answer wasn't
+    /// found gets a comment in place of its test.
+    pub fn test_module(&self) -> String {
+        let input2 = if self.input2.is_some() {
+            "INPUT2"
+        } else {
+            "INPUT"
+        };
+        let tests = [("example", "INPUT"), ("example_part2", input2)];
+        let tests = (1..).zip(tests).zip(&self.answers).collect::<Vec<_>>();
+
+        // Only the inputs that a test uses, so that none are unused
+        let used = |input| {
+            tests
+                .iter()
+                .any(|((_, (_, i)), a)| *i == input && a.is_some())
+        };
+
+        let mut s = String::new();
+
+        s.push_str("#[cfg(test)]\nmod test {\n    use super::*;\n\n");
+        if used("INPUT") {
+            s.push_str("    const INPUT: &str = include_str!(\"../input.test\");\n");
+        }
+        if used("INPUT2") {
+            s.push_str("    const INPUT2: &str = include_str!(\"../input2.test\");\n");
+        }
+
+        for &((part, (name, input)), answer) in &tests {
+            let Some(answer) = answer else {
+                writeln!(s, "\n    // part {part}: no example answer found")
+                    .expect("Writing to a String cannot fail");
+                continue;
+            };
+            let expected = literal(answer);
+
+            write!(
+                s,
+                "\n    \
+                 #[test]\n    \
+                 #[snafu::report]\n    \
+                 fn {name}() -> Result<()> {{\n        \
+                     assert_eq!({expected}, part{part}({input})?);\n        \
+                     Ok(())\n    \
+                 }}\n",
+            )
+            .expect("Writing to a String cannot fail");
+        }
+
+        s.push_str("}\n");
+        s
+    }
+}
+
+/// The first `<pre><code>` block whose preceding paragraph
+/// introduces it as an example, such as "For example:" or "Here's a
+/// larger example:". Blocks after "the example above, ...:" continue
+/// the earlier example instead.
+fn introduced_example(article: &str) -> Option<String> {
+    let mut pieces = article.split("<pre><code>");
+    let mut before = pieces.next()?;
+
+    for piece in pieces {
+        let (block, after) = piece.split_once("</code></pre>")?;
+        let introduction = contents(before, "<p>", "</p>")
+            .last()
+            .map(|p| text(p).trim_end().to_lowercase())
+            .unwrap_or_default();
+
+        if introduction.contains("for example") || introduction.ends_with("example:") {
+            return Some(text(block));
+        }
+        before = after;
+    }
+
+    None
+}
+
+/// Numbers are left bare; anything else becomes a string.
+fn literal(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        answer.to_owned()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// The text between each `start` and the following `end`. When
+/// `start` is an opening tag without its `>`, its attributes are
+/// skipped.
+fn contents<'a>(s: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = s;
+
+    std::iter::from_fn(move || {
+        let (_, after) = rest.split_once(start)?;
+        let after = if start.ends_with('>') {
+            after
+        } else {
+            after.split_once('>')?.1
+        };
+        let (inside, after) = after.split_once(end)?;
+        rest = after;
+        Some(inside)
+    })
+}
+
+/// Removes any markup and decodes the entities that the puzzles use.
+fn text(html: &str) -> String {
+    let mut s = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(i) = rest.find(['<', '&']) {
+        s.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if rest.starts_with('<') {
+            rest = rest.split_once('>').map_or("", |(_, r)| r);
+            continue;
+        }
+
+        let entities = [
+            ("&lt;", '<'),
+            ("&gt;", '>'),
+            ("&amp;", '&'),
+            ("&quot;", '"'),
+            ("&#39;", '\''),
+            ("&apos;", '\''),
+        ];
+        match entities.iter().find(|(e, _)| rest.starts_with(e)) {
+            Some(&(e, c)) => {
+                s.push(c);
+                rest = &rest[e.len()..];
+            }
+            None => {
+                s.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    s.push_str(rest);
+    s
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ExamplesError {
+    #[snafu(display("The page has no puzzle description; was it saved while logged in?"))]
+    DescriptionMissing,
+
+    #[snafu(display("The puzzle description has no example"))]
+    ExampleMissing,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+ 1   2
+
+move 1 from 2 to 1
+</code></pre>
+<p>After the crates are moved, the top crates are <code><em>CMZ</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>VGBBJCRMN</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Again considering the example above, the crates begin in the same configuration:</p>
+<pre><code>[<em>D</em>] &lt;- moved</code></pre>
+<p>In this example, the CrateMover 9001 puts the crates in a totally different order: <em><code>MCD</code></em>.</p>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn extracts_the_example() {
+        let examples = Examples::from_html(PAGE).unwrap();
+
+        assert_eq!(
+            "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\n",
+            examples.input,
+        );
+        assert_eq!(None, examples.input2);
+        assert_eq!(
+            vec![Some("CMZ".to_owned()), Some("MCD".to_owned())],
+            examples.answers,
+        );
+    }
+
+    #[test]
+    fn only_the_first_part() {
+        let (page, _) = PAGE.split_once("<p>Your puzzle answer").unwrap();
+        let examples = Examples::from_html(page).unwrap();
+
+        assert_eq!(None, examples.input2);
+        assert_eq!(vec![Some("CMZ".to_owned())], examples.answers);
+    }
+
+    #[test]
+    fn part2_introducing_a_new_example() {
+        let page = "<article><p>For example:</p><pre><code>R 4\n</code></pre></article>\
+                    <article><p>These knots move as follows:</p><pre><code>H.\n</code></pre>\
+                    <p>Here's a larger example:</p><pre><code>R 5\n</code></pre></article>";
+        let examples = Examples::from_html(page).unwrap();
+
+        assert_eq!("R 4\n", examples.input);
+        assert_eq!(Some("R 5\n"), examples.input2.as_deref());
+    }
+
+    #[test]
+    fn part2_repeating_the_example() {
+        let page = "<article><p>For example:</p><pre><code>1\n</code></pre></article>\
+                    <article><p>For example:</p><pre><code>1\n</code></pre></article>";
+        let examples = Examples::from_html(page).unwrap();
+
+        assert_eq!("1\n", examples.input);
+        assert_eq!(None, examples.input2);
+    }
+
+    #[test]
+    fn test_module() {
+        let examples = Examples {
+            input: String::new(),
+            input2: Some(String::new()),
+            answers: vec![Some("95437".into()), None],
+        };
+
+        assert_eq!(
+            r#"#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(95437, part1(INPUT)?);
+        Ok(())
+    }
+
+    // part 2: no example answer found
+}
+"#,
+            examples.test_module(),
+        );
+    }
+
+    #[test]
+    fn markup_is_removed() {
+        assert_eq!(
+            "a <b> & 'c'",
+            text("a &lt;<em>b</em>&gt; &amp; &#39;c&#39;")
+        );
+        assert_eq!("AT&T", text("AT&T"));
+    }
+
+    #[test]
+    fn pages_without_examples_are_rejected() {
+        assert!(matches!(
+            Examples::from_html("<html>Please log in</html>"),
+            Err(ExamplesError::DescriptionMissing)
+        ));
+        assert!(matches!(
+            Examples::from_html("<article><p>No example</p></article>"),
+            Err(ExamplesError::ExampleMissing)
+        ));
+    }
+}
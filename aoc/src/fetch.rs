@@ -0,0 +1,128 @@
+use crate::site::{Site, SiteArgs, SiteError};
+use snafu::prelude::*;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// What [`fetch`] did.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Downloads the input for `day` to `path`, unless it is already
+/// there. Nothing is sent to the website for a cached input.
+///
+/// The input is written next to `path` first and then renamed, so an
+/// interrupted download is never mistaken for a cached one.
+pub fn fetch(site: SiteArgs, day: u8, path: &Path) -> Result<Fetched, FetchError> {
+    use fetch_error::*;
+
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let directory = path.parent().unwrap_or(Path::new("."));
+    ensure!(directory.is_dir(), DirectoryMissingSnafu { directory });
+
+    let site = Site::new(site).context(SiteSnafu { day })?;
+    let input = site.input(day).context(SiteSnafu { day })?;
+
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input).context(WriteSnafu { path: &partial })?;
+    fs::rename(&partial, path).context(WriteSnafu { path })?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum FetchError {
+    #[snafu(display("{} does not exist", directory.display()))]
+    DirectoryMissing { directory: PathBuf },
+
+    #[snafu(display("Could not download the input for day {day}"))]
+    Site { source: SiteError, day: u8 },
+
+    #[snafu(display("Could not save the input to {}", path.display()))]
+    Write { source: io::Error, path: PathBuf },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_server::{TempDir, TestServer};
+
+    fn site(server: &TestServer) -> SiteArgs {
+        SiteArgs {
+            session: Some("abc123".into()),
+            base_url: format!("{}/", server.base_url()),
+        }
+    }
+
+    #[test]
+    fn downloads_once() {
+        let server = TestServer::start([(200, "1\n2\n")]);
+        let dir = TempDir::new("fetch-once");
+        let path = dir.path().join("input");
+
+        assert_eq!(Fetched::Downloaded, fetch(site(&server), 7, &path).unwrap());
+        assert_eq!(Fetched::Cached, fetch(site(&server), 7, &path).unwrap());
+        assert_eq!("1\n2\n", fs::read_to_string(&path).unwrap());
+
+        let requests = server.finish();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/day/7/input", requests[0].url);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+    }
+
+    #[test]
+    fn failures_are_not_cached() {
+        let server = TestServer::start([(400, "Please log in\n")]);
+        let dir = TempDir::new("fetch-failure");
+        let path = dir.path().join("input");
+
+        let error = fetch(site(&server), 7, &path).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                FetchError::Site {
+                    source: SiteError::Status { status: 400, body, .. },
+                    ..
+                } if body == "Please log in\n",
+            ),
+            "{error:?}",
+        );
+        assert!(!path.exists());
+
+        server.finish();
+    }
+
+    #[test]
+    fn a_session_is_required() {
+        let server = TestServer::start([]);
+        let dir = TempDir::new("fetch-session");
+        let path = dir.path().join("input");
+
+        let site = SiteArgs {
+            session: None,
+            ..site(&server)
+        };
+        let error = fetch(site, 7, &path).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                FetchError::Site {
+                    source: SiteError::SessionMissing,
+                    ..
+                },
+            ),
+            "{error:?}",
+        );
+
+        assert!(server.finish().is_empty());
+    }
+}
@@ -0,0 +1,560 @@
+use aoc_common::{input::InputSource, AnswerCheck, Format, InputHash, Record, Verdict};
+use clap::{Parser, Subcommand};
+use fetch::Fetched;
+use site::SiteArgs;
+use snafu::prelude::*;
+use std::{
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    time::{Instant, SystemTime},
+};
+
+mod allocations;
+mod days;
+mod examples;
+mod fetch;
+mod scaffold;
+mod site;
+mod submit;
+#[cfg(test)]
+mod test_server;
+
+/// Runs the Advent of Code solutions
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solves puzzles, printing each answer and how long it took
+    ///
+    /// When built with the `count-allocations` feature, also prints
+    /// what each part allocated, and the most heap memory it had in
+    /// use at once. That peak is of live heap memory, not of the
+    /// process's resident memory.
+    Run {
+        /// The day to solve, or `all`
+        day: DaySelection,
+
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Read the puzzle input from this file (`-` for stdin)
+        /// instead of the day's bundled input
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Record the answers as the confirmed answers for the input
+        #[arg(long)]
+        confirm: bool,
+
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+
+    /// Prints a random puzzle input
+    Generate {
+        /// The day to generate an input for
+        day: u8,
+
+        /// The same seed and size always produce the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big the input should be; what this counts depends on
+        /// the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+
+    /// Runs every implementation of each part on the same inputs and
+    /// reports any that disagree
+    Verify {
+        /// The day to verify, or `all`
+        day: DaySelection,
+
+        /// Read the puzzle input from this file (`-` for stdin)
+        /// instead of the day's bundled input
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Also verify this many generated inputs, one per seed
+        /// starting from `--seed`
+        #[arg(long, default_value_t = 0)]
+        generated: u64,
+
+        /// The seed of the first generated input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big the generated inputs should be
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+
+    /// Downloads a day's puzzle input into its crate, unless it has
+    /// already been downloaded
+    Fetch {
+        /// The day to download the input for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+
+    /// Creates the crate for a day and adds it to the runner
+    New {
+        /// The day to create
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+
+    /// Saves the examples from a puzzle's description as the day's
+    /// `input.test` (and `input2.test` when part 2 has its own), and
+    /// prints tests of them against the example answers
+    Examples {
+        /// The day the puzzle is for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The puzzle's page, as saved by a browser
+        html: PathBuf,
+
+        /// Replace existing examples
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Submits an answer to the website, unless earlier guesses show
+    /// that it is wrong
+    Submit {
+        /// The day to submit an answer for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The part to submit an answer for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Submit this instead of the answer computed from the day's
+        /// input
+        #[arg(long)]
+        answer: Option<String>,
+
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+}
+
+#[derive(Debug, Copy, Clone)]
+enum DaySelection {
+    All,
+    One(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            s => s.parse().map(Self::One),
+        }
+    }
+}
+
+#[snafu::report]
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            confirm,
+            format,
+        } => run(day, part, input, confirm, format),
+        Command::Generate { day, seed, size } => generate(day, seed, size),
+        Command::Verify {
+            day,
+            input,
+            generated,
+            seed,
+            size,
+        } => verify(day, input, generated, seed, size),
+        Command::Fetch { day, site } => fetch(day, site),
+        Command::New { day } => new_day(day),
+        Command::Examples { day, html, force } => extract_examples(day, html, force),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        } => submit(day, part, answer, site),
+    }
+}
+
+fn run(
+    day: DaySelection,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    confirm: bool,
+    format: Format,
+) -> Result<()> {
+    let days = match day {
+        DaySelection::All => {
+            ensure!(input.is_none(), InputWithAllDaysSnafu);
+            days::DAYS.iter().collect::<Vec<_>>()
+        }
+        DaySelection::One(day) => vec![days::find(day).context(UnknownDaySnafu { day })?],
+    };
+
+    let mut failures = 0_usize;
+
+    for day in days {
+        if let Err(e) = run_day(day, part, input.clone(), confirm, format) {
+            // Failed parts have already been recorded, along with the
+            // input they failed on
+            if format == Format::Json && !matches!(e, Error::Part { .. }) {
+                Record::error(day.number, None, None, &e).print();
+            }
+            eprintln!("{}", snafu::Report::from_error(e));
+            failures += 1;
+        }
+    }
+
+    ensure!(failures == 0, FailuresSnafu { failures });
+
+    Ok(())
+}
+
+fn run_day(
+    day: &days::Day,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    confirm: bool,
+    format: Format,
+) -> Result<()> {
+    let number = day.number;
+
+    let source = InputSource::from_arg(input.map(PathBuf::into_os_string), day.input_path);
+    let input = source.read().context(InputSnafu { day: number })?;
+    let mut answers = AnswerCheck::new(day.answers_path, &input, confirm)
+        .context(AnswersSnafu { day: number })?;
+
+    // Both parts share the parsed input, so neither is charged for it
+    let parsed = (day.parse)(&input).context(ParseSnafu { day: number })?;
+
+    for part_number in 1..=2 {
+        if part.is_some_and(|p| p != part_number) {
+            continue;
+        }
+
+        let start = Instant::now();
+        let (answer, usage) = allocations::measure(|| parsed.part(part_number));
+        let elapsed = start.elapsed();
+        let answer = answer.context(PartSnafu {
+            day: number,
+            part: part_number,
+        });
+
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                if format == Format::Json {
+                    Record::error(number, Some(part_number), Some(answers.hash()), &e).print();
+                }
+                return Err(e);
+            }
+        };
+
+        let verdict = answers.check(part_number, &answer);
+
+        if format == Format::Json {
+            let record = Record::answer(number, part_number, &answer, elapsed, answers.hash());
+            let record = match usage {
+                Some(u) => record.with_allocations(u.allocations, u.bytes, u.peak_heap_bytes),
+                None => record,
+            };
+            let record = if confirm {
+                record
+            } else {
+                record.with_verdict(&verdict)
+            };
+            record.print();
+            continue;
+        }
+
+        let verdict = match verdict {
+            Verdict::Unknown | Verdict::Wrong { .. } if confirm => "recorded".to_string(),
+            verdict => verdict.to_string(),
+        };
+
+        let cost = match usage {
+            Some(usage) => format!("{elapsed:?}, {usage}"),
+            None => format!("{elapsed:?}"),
+        };
+
+        let answer = answer.trim_end();
+        if answer.contains('\n') {
+            println!("Day {number:02} part {part_number} ({cost}) [{verdict}]:\n{answer}");
+        } else {
+            println!("Day {number:02} part {part_number} ({cost}) [{verdict}]: {answer}");
+        }
+    }
+
+    answers.finish().context(AnswersSnafu { day: number })?;
+
+    Ok(())
+}
+
+fn verify(
+    day: DaySelection,
+    input: Option<PathBuf>,
+    generated: u64,
+    seed: u64,
+    size: usize,
+) -> Result<()> {
+    let days = match day {
+        DaySelection::All => {
+            ensure!(input.is_none(), InputWithAllDaysSnafu);
+            days::DAYS.iter().collect::<Vec<_>>()
+        }
+        DaySelection::One(day) => vec![days::find(day).context(UnknownDaySnafu { day })?],
+    };
+
+    let mut disagreements = 0_usize;
+
+    for day in days {
+        let number = day.number;
+
+        let source =
+            InputSource::from_arg(input.clone().map(PathBuf::into_os_string), day.input_path);
+        let description = match &input {
+            Some(path) => format!("the input {}", path.display()),
+            None => "the bundled input".to_owned(),
+        };
+        let real = source.read().context(InputSnafu { day: number })?;
+
+        let generated = (seed..seed.saturating_add(generated)).map(|seed| {
+            let description =
+                format!("the input from `aoc generate {number} --seed {seed} --size {size}`");
+            (description, (day.generate)(seed, size))
+        });
+
+        let mut implementations = [0; 2];
+
+        for (description, input) in std::iter::once((description, real)).chain(generated) {
+            let answers = (day.verify)(&input).context(VerifyParseSnafu {
+                day: number,
+                input: &description,
+            })?;
+
+            for ((part, answers), n) in (1..).zip(answers).zip(&mut implementations) {
+                *n = answers.0.len();
+
+                if answers.agree() {
+                    continue;
+                }
+
+                disagreements += 1;
+                println!("Day {number:02} part {part} disagrees on {description}:");
+                for (name, answer) in answers.0 {
+                    match answer {
+                        Ok(answer) => println!("  {name}: {}", answer.trim_end()),
+                        Err(e) => println!("  {name} failed: {e}"),
+                    }
+                }
+            }
+        }
+
+        let [part1, part2] = implementations;
+        println!("Day {number:02}: {part1} implementation(s) of part 1, {part2} of part 2 checked");
+    }
+
+    ensure!(disagreements == 0, DisagreementsSnafu { disagreements });
+
+    Ok(())
+}
+
+fn generate(day: u8, seed: u64, size: usize) -> Result<()> {
+    let day = days::find(day).context(UnknownDaySnafu { day })?;
+    print!("{}", (day.generate)(seed, size));
+    Ok(())
+}
+
+fn fetch(day: u8, site: SiteArgs) -> Result<()> {
+    let path = days::directory(day).join("input");
+
+    match fetch::fetch(site, day, &path)? {
+        Fetched::Cached => eprintln!("Day {day:02} input is already at {}", path.display()),
+        Fetched::Downloaded => eprintln!("Day {day:02} input saved to {}", path.display()),
+    }
+
+    Ok(())
+}
+
+fn new_day(day: u8) -> Result<()> {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+    let directory = scaffold::new_day(root.as_ref(), day)?;
+    println!("Day {day:02} created in {}", directory.display());
+
+    Ok(())
+}
+
+fn extract_examples(day: u8, html: PathBuf, force: bool) -> Result<()> {
+    let page = fs::read_to_string(&html).context(PageSnafu { path: &html })?;
+    let examples = examples::Examples::from_html(&page)?;
+
+    let directory = days::directory(day);
+    ensure!(directory.is_dir(), DayMissingSnafu { day });
+
+    let mut files = vec![(directory.join("input.test"), &examples.input)];
+    if let Some(input2) = &examples.input2 {
+        files.push((directory.join("input2.test"), input2));
+    }
+
+    // `aoc new` leaves an empty `input.test` to be filled in
+    for (path, _) in &files {
+        let unused = fs::read_to_string(path).map_or(true, |s| s.is_empty());
+        ensure!(force || unused, ExampleExistsSnafu { path });
+    }
+
+    for (path, contents) in files {
+        fs::write(&path, contents).context(ExampleWriteSnafu { path: &path })?;
+        eprintln!("Day {day:02} example saved to {}", path.display());
+    }
+    print!("{}", examples.test_module());
+
+    Ok(())
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>, site: SiteArgs) -> Result<()> {
+    let number = day;
+    let day = days::find(day).context(UnknownDaySnafu { day })?;
+
+    let source = InputSource::Path(day.input_path.into());
+    let input = source.read().context(InputSnafu { day: number })?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let parsed = (day.parse)(&input).context(ParseSnafu { day: number })?;
+            parsed.part(part).context(PartSnafu { day: number, part })?
+        }
+    };
+    let answer = answer.trim_end();
+
+    let submission = submit::Submission {
+        day: number,
+        part,
+        hash: InputHash::of(&input),
+        answer,
+    };
+    let guesses_path = days::directory(number).join("guesses");
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let outcome = submit::submit(
+        site,
+        &guesses_path,
+        day.answers_path.as_ref(),
+        submission,
+        now,
+    )
+    .context(SubmitSnafu { day: number, part })?;
+    println!("Day {number:02} part {part}: {answer} is {outcome}");
+
+    Ok(())
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("There is no solution for day {day}"))]
+    UnknownDay { day: u8 },
+
+    #[snafu(display("An input file cannot be used when running all days"))]
+    InputWithAllDays,
+
+    #[snafu(display("Could not load the input for day {day}"))]
+    Input {
+        source: aoc_common::InputError,
+        day: u8,
+    },
+
+    #[snafu(display("Could not check the answers for day {day}"))]
+    Answers {
+        source: aoc_common::AnswersError,
+        day: u8,
+    },
+
+    #[snafu(display("Could not parse the input for day {day}"))]
+    Parse {
+        source: Box<dyn std::error::Error>,
+        day: u8,
+    },
+
+    #[snafu(display("Day {day} part {part} failed"))]
+    Part {
+        source: Box<dyn std::error::Error>,
+        day: u8,
+        part: u8,
+    },
+
+    #[snafu(display("{failures} day(s) failed"))]
+    Failures { failures: usize },
+
+    #[snafu(display("Day {day} could not parse {input}"))]
+    VerifyParse {
+        source: Box<dyn std::error::Error>,
+        day: u8,
+        input: String,
+    },
+
+    #[snafu(display("{disagreements} part(s) had implementations that disagree"))]
+    Disagreements { disagreements: usize },
+
+    #[snafu(context(false), display("Could not fetch the input"))]
+    Fetch { source: fetch::FetchError },
+
+    #[snafu(display("Could not submit day {day} part {part}"))]
+    Submit {
+        source: submit::SubmitError,
+        day: u8,
+        part: u8,
+    },
+
+    #[snafu(context(false), display("Could not create the new day"))]
+    Scaffold { source: scaffold::ScaffoldError },
+
+    #[snafu(display("Could not read the puzzle page {}", path.display()))]
+    Page {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(context(false), display("Could not extract the examples"))]
+    Examples { source: examples::ExamplesError },
+
+    #[snafu(display("There is no crate for day {day} yet"))]
+    DayMissing { day: u8 },
+
+    #[snafu(display("{} already exists; use --force to replace it", path.display()))]
+    ExampleExists { path: PathBuf },
+
+    #[snafu(display("Could not save the example to {}", path.display()))]
+    ExampleWrite {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,347 @@
+use snafu::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The files of a new day's crate, relative to its directory. `{{day}}`
+/// is replaced by the zero-padded day and `{{number}}` by the day
+/// itself.
+const TEMPLATE: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../template/Cargo.toml")),
+    ("src/lib.rs", include_str!("../template/lib.rs")),
+    ("src/main.rs", include_str!("../template/main.rs")),
+    ("src/generate.rs", include_str!("../template/generate.rs")),
+    // Filled in by `aoc examples`
+    ("input.test", ""),
+];
+
+const FUZZ_TARGET: &str = "\
+#![no_main]
+
+use aoc_common::Solution;
+use day_{{day}}::Day{{day}};
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day{{day}}::parse(s) {
+        let _ = Day{{day}}::part1(&parsed, Day{{day}}::PART1_PARAMS);
+        let _ = Day{{day}}::part2(&parsed, Day{{day}}::PART2_PARAMS);
+    }
+});
+";
+
+const FUZZ_BIN: &str = "\
+[[bin]]
+name = \"day-{{day}}\"
+path = \"fuzz_targets/day_{{day}}.rs\"
+test = false
+doc = false
+bench = false
+";
+
+/// Recognizes the lines of a file that register a day, and which day
+/// they are for.
+type DayKey = fn(&str) -> Option<u8>;
+
+/// Creates the crate for `day` in the workspace at `root` and adds it
+/// to the runner, the benchmarks, and the fuzz targets.
+///
+/// Every change is worked out before anything is written, so a
+/// workspace that can't take the new day is left alone.
+pub fn new_day(root: &Path, day: u8) -> Result<PathBuf, ScaffoldError> {
+    use scaffold_error::*;
+
+    let directory = root.join(format!("day-{day:02}"));
+    ensure!(!directory.exists(), DayExistsSnafu { path: directory });
+
+    let fill = |template: &str| {
+        template
+            .replace("{{day}}", &format!("{day:02}"))
+            .replace("{{number}}", &day.to_string())
+    };
+
+    let mut files = TEMPLATE
+        .iter()
+        .map(|(name, template)| (directory.join(name), fill(template)))
+        .collect::<Vec<_>>();
+
+    let registrations: [(&str, DayKey, String); 4] = [
+        (
+            "aoc/Cargo.toml",
+            |l| l.strip_prefix("day-")?.split_once(" = ")?.0.parse().ok(),
+            fill(r#"day-{{day}} = { path = "../day-{{day}}" }"#),
+        ),
+        (
+            "aoc/src/days.rs",
+            |l| l.trim().strip_prefix("day!(day_")?.get(..2)?.parse().ok(),
+            fill(r#"    day!(day_{{day}}::Day{{day}}, "day-{{day}}"),"#),
+        ),
+        (
+            "aoc/benches/days.rs",
+            |l| {
+                l.trim()
+                    .strip_prefix("bench_day::<day_")?
+                    .get(..2)?
+                    .parse()
+                    .ok()
+            },
+            fill(r#"    bench_day::<day_{{day}}::Day{{day}}>(c, "day-{{day}}", &[10]);"#),
+        ),
+        (
+            "fuzz/Cargo.toml",
+            |l| l.strip_prefix("day-")?.split_once(" = ")?.0.parse().ok(),
+            fill(r#"day-{{day}} = { path = "../day-{{day}}" }"#),
+        ),
+    ];
+
+    for (name, key, line) in registrations {
+        let path = root.join(name);
+        let contents = read(&path)?;
+        let contents = insert_line(&contents, day, key, &line)
+            .context(RegistrationMissingSnafu { path: &path })?;
+        files.push((path, contents));
+    }
+
+    let fuzz_manifest = files.last_mut().expect("The fuzz manifest was just added");
+    fuzz_manifest.1 = insert_bin(&fuzz_manifest.1, day, &fill(FUZZ_BIN));
+
+    let fuzz_target = root.join(fill("fuzz/fuzz_targets/day_{{day}}.rs"));
+    files.push((fuzz_target, fill(FUZZ_TARGET)));
+
+    fs::create_dir_all(directory.join("src")).context(CreateSnafu { path: &directory })?;
+    for (path, contents) in files {
+        fs::write(&path, contents).context(WriteSnafu { path })?;
+    }
+
+    Ok(directory)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).context(scaffold_error::ReadSnafu { path })
+}
+
+/// Adds `line` among the lines that `key` recognizes as belonging to a
+/// day, keeping them in order.
+fn insert_line(
+    contents: &str,
+    day: u8,
+    key: impl Fn(&str) -> Option<u8>,
+    line: &str,
+) -> Option<String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, key(l)?)))
+        .collect::<Vec<_>>();
+    let (first, _) = days.first()?;
+
+    let at = days
+        .iter()
+        .rev()
+        .find(|&&(_, d)| d < day)
+        .map_or(*first, |(i, _)| i + 1);
+    lines.insert(at, line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds the `[[bin]]` section before the first one for a later day.
+fn insert_bin(contents: &str, day: u8, section: &str) -> String {
+    let later = contents.match_indices("[[bin]]\n").find(|&(i, _)| {
+        let name = contents[i..].lines().nth(1).unwrap_or_default();
+        let number = name
+            .strip_prefix("name = \"day-")
+            .and_then(|n| n.strip_suffix('"'))
+            .and_then(|n| n.parse::<u8>().ok());
+        number.is_some_and(|n| n > day)
+    });
+
+    match later {
+        Some((i, _)) => format!("{}{section}\n{}", &contents[..i], &contents[i..]),
+        None => format!("{}\n\n{section}", contents.trim_end()),
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ScaffoldError {
+    #[snafu(display("{} already exists", path.display()))]
+    DayExists { path: PathBuf },
+
+    #[snafu(display("Could not read {}", path.display()))]
+    Read {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not find where to add the day in {}", path.display()))]
+    RegistrationMissing { path: PathBuf },
+
+    #[snafu(display("Could not create {}", path.display()))]
+    Create {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not write {}", path.display()))]
+    Write {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_server::TempDir;
+
+    /// The parts of a workspace that register days, with days 1 and 3.
+    const FIXTURE: &[(&str, &str)] = &[
+        (
+            "aoc/Cargo.toml",
+            r#"[dependencies]
+aoc-common = { workspace = true }
+day-01 = { path = "../day-01" }
+day-03 = { path = "../day-03" }
+
+[dev-dependencies]
+"#,
+        ),
+        (
+            "aoc/src/days.rs",
+            r#"pub static DAYS: &[Day] = &[
+    day!(day_01::Day01, "day-01"),
+    day!(day_03::Day03, "day-03"),
+];
+"#,
+        ),
+        (
+            "aoc/benches/days.rs",
+            r#"fn benches(c: &mut Criterion) {
+    bench_day::<day_01::Day01>(c, "day-01", &[10]);
+    bench_day::<day_03::Day03>(c, "day-03", &[10]);
+}
+"#,
+        ),
+        (
+            "fuzz/Cargo.toml",
+            r#"[dependencies]
+libfuzzer-sys = "0.4"
+
+day-01 = { path = "../day-01" }
+day-03 = { path = "../day-03" }
+
+[[bin]]
+name = "day-01"
+path = "fuzz_targets/day_01.rs"
+
+[[bin]]
+name = "day-03"
+path = "fuzz_targets/day_03.rs"
+"#,
+        ),
+    ];
+
+    fn workspace(name: &str) -> TempDir {
+        let root = TempDir::new(name);
+
+        for (name, contents) in FIXTURE {
+            let path = root.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        fs::create_dir_all(root.path().join("fuzz/fuzz_targets")).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn creates_and_registers_the_day() {
+        let root = workspace("scaffold-new");
+        let directory = new_day(root.path(), 2).unwrap();
+        let file = |name: &str| fs::read_to_string(root.path().join(name)).unwrap();
+
+        let lib = fs::read_to_string(directory.join("src/lib.rs")).unwrap();
+        assert!(lib.starts_with("//! Day 2\n"), "{lib}");
+        assert!(lib.contains("pub struct Day02;"), "{lib}");
+        assert!(lib.contains("const DAY: u8 = 2;"), "{lib}");
+        assert!(!lib.contains("{{"), "{lib}");
+        assert!(directory.join("input.test").exists());
+
+        assert_eq!(
+            r#"pub static DAYS: &[Day] = &[
+    day!(day_01::Day01, "day-01"),
+    day!(day_02::Day02, "day-02"),
+    day!(day_03::Day03, "day-03"),
+];
+"#,
+            file("aoc/src/days.rs"),
+        );
+
+        let manifest = file("aoc/Cargo.toml");
+        assert!(
+            manifest.contains("day-02 = { path = \"../day-02\" }\nday-03"),
+            "{manifest}",
+        );
+
+        let benches = file("aoc/benches/days.rs");
+        assert!(
+            benches.contains("bench_day::<day_02::Day02>(c, \"day-02\", &[10]);"),
+            "{benches}",
+        );
+
+        let fuzz = file("fuzz/Cargo.toml");
+        assert!(
+            fuzz.contains(
+                "path = \"fuzz_targets/day_02.rs\"\ntest = false\ndoc = false\nbench = false\n\n\
+                 [[bin]]\nname = \"day-03\""
+            ),
+            "{fuzz}"
+        );
+        assert!(root.path().join("fuzz/fuzz_targets/day_02.rs").exists());
+    }
+
+    #[test]
+    fn days_are_kept_in_order() {
+        assert_eq!(
+            "a\nday-01 = 1\nday-02 = 2\nday-03 = 3\nb\n",
+            insert_line(
+                "a\nday-01 = 1\nday-03 = 3\nb\n",
+                2,
+                |l| l.strip_prefix("day-")?.split_once(" = ")?.0.parse().ok(),
+                "day-02 = 2",
+            )
+            .unwrap(),
+        );
+
+        let bins = "[[bin]]\nname = \"day-01\"\n\n[[bin]]\nname = \"day-03\"\n";
+        assert_eq!(
+            "[[bin]]\nname = \"day-01\"\n\n[[bin]]\nname = \"day-02\"\n\n[[bin]]\nname = \"day-03\"\n",
+            insert_bin(bins, 2, "[[bin]]\nname = \"day-02\"\n"),
+        );
+    }
+
+    #[test]
+    fn existing_days_are_left_alone() {
+        let root = workspace("scaffold-exists");
+        fs::create_dir(root.path().join("day-03")).unwrap();
+        let before = fs::read_to_string(root.path().join("aoc/src/days.rs")).unwrap();
+
+        assert!(matches!(
+            new_day(root.path(), 3),
+            Err(ScaffoldError::DayExists { .. })
+        ));
+        let after = fs::read_to_string(root.path().join("aoc/src/days.rs")).unwrap();
+        assert_eq!(before, after);
+    }
+}
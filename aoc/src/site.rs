@@ -0,0 +1,121 @@
+use crate::submit::Outcome;
+use snafu::prelude::*;
+use std::io;
+
+/// Where the puzzles are published.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// How to reach the Advent of Code website, or something that behaves
+/// like it.
+#[derive(Debug, Clone, clap::Args)]
+pub struct SiteArgs {
+    /// The `session` cookie of a logged-in browser
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: Option<String>,
+
+    /// The address of the year's puzzles
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    pub base_url: String,
+}
+
+/// A logged-in client for the website.
+#[derive(Debug)]
+pub struct Site {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Site {
+    pub fn new(args: SiteArgs) -> Result<Self, SiteError> {
+        use site_error::*;
+
+        let SiteArgs { session, base_url } = args;
+        let session = session.context(SessionMissingSnafu)?;
+        let base_url = base_url.trim_end_matches('/').to_owned();
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!("aoc/", env!("CARGO_PKG_VERSION")))
+            .build();
+
+        Ok(Self {
+            base_url,
+            session,
+            agent,
+        })
+    }
+
+    /// The puzzle input for `day`.
+    pub fn input(&self, day: u8) -> Result<String, SiteError> {
+        let url = format!("{}/day/{day}/input", self.base_url);
+        let response = self.logged_in(self.agent.get(&url)).call();
+        let response = check_status(response, &url)?;
+        read_body(response, &url)
+    }
+
+    /// Submits `answer` for one part of `day`.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Outcome, SiteError> {
+        let url = format!("{}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let response = self
+            .logged_in(self.agent.post(&url))
+            .send_form(&[("level", &level), ("answer", answer)]);
+        let response = check_status(response, &url)?;
+        let body = read_body(response, &url)?;
+
+        Outcome::from_response(&body).context(site_error::ResponseUnrecognizedSnafu { url, body })
+    }
+
+    fn logged_in(&self, request: ureq::Request) -> ureq::Request {
+        request.set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+/// Unsuccessful responses become errors that include their body, as
+/// the website explains most problems there.
+fn check_status(
+    response: Result<ureq::Response, ureq::Error>,
+    url: &str,
+) -> Result<ureq::Response, SiteError> {
+    use site_error::*;
+
+    match response {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = read_body(response, url)?;
+            StatusSnafu { url, status, body }.fail()
+        }
+        Err(ureq::Error::Transport(e)) => Err(Box::new(e)).context(TransportSnafu { url }),
+    }
+}
+
+fn read_body(response: ureq::Response, url: &str) -> Result<String, SiteError> {
+    response
+        .into_string()
+        .context(site_error::BodySnafu { url })
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum SiteError {
+    #[snafu(display("A session token is needed; set AOC_SESSION or use --session"))]
+    SessionMissing,
+
+    #[snafu(display("Could not reach {url}"))]
+    Transport {
+        source: Box<ureq::Transport>,
+        url: String,
+    },
+
+    #[snafu(display("{url} responded with status {status}: {}", body.trim()))]
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+
+    #[snafu(display("Could not read the response from {url}"))]
+    Body { source: io::Error, url: String },
+
+    #[snafu(display("The response from {url} was not understood: {}", body.trim()))]
+    ResponseUnrecognized { url: String, body: String },
+}
@@ -0,0 +1,598 @@
+use crate::site::{Site, SiteArgs, SiteError};
+use aoc_common::{answers::Answers, AnswersError, InputHash};
+use snafu::prelude::*;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The website makes you wait at least this long after a wrong answer.
+const WRONG_ANSWER_DELAY_S: u64 = 60;
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Wrong,
+    /// Submitted too soon after the previous answer; it was not
+    /// checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part is already solved or not yet available.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Understands the text of the website's response page.
+    pub fn from_response(body: &str) -> Option<Self> {
+        Some(if body.contains("That's the right answer") {
+            Self::Correct
+        } else if body.contains("That's not the right answer") {
+            if body.contains("your answer is too high") {
+                Self::TooHigh
+            } else if body.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if body.contains("You gave an answer too recently") {
+            Self::Wait {
+                seconds: time_left(body).unwrap_or(WRONG_ANSWER_DELAY_S),
+            }
+        } else if body.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            return None;
+        })
+    }
+
+    fn is_incorrect(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+/// Finds the `1m 23s` in "You have 1m 23s left to wait".
+fn time_left(body: &str) -> Option<u64> {
+    let (before, _) = body.split_once(" left to wait")?;
+    let (_, time) = before.rsplit_once("You have ")?;
+
+    time.split_whitespace().try_fold(0, |total, part| {
+        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        let scale = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            _ => return None,
+        };
+        Some(total + n * scale)
+    })
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => "correct".fmt(f),
+            Self::TooHigh => "too-high".fmt(f),
+            Self::TooLow => "too-low".fmt(f),
+            Self::Wrong => "wrong".fmt(f),
+            Self::Wait { seconds } => write!(f, "wait-{seconds}s"),
+            Self::WrongLevel => "wrong-level".fmt(f),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Self::Correct,
+            "too-high" => Self::TooHigh,
+            "too-low" => Self::TooLow,
+            "wrong" => Self::Wrong,
+            "wrong-level" => Self::WrongLevel,
+            s => {
+                let seconds = s.strip_prefix("wait-").and_then(|s| s.strip_suffix('s'));
+                let seconds = seconds.ok_or(())?.parse().map_err(drop)?;
+                Self::Wait { seconds }
+            }
+        })
+    }
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub hash: InputHash,
+    pub part: u8,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+/// Every answer submitted for each input, oldest first.
+///
+/// Stored as one line per guess: the input hash, the part, when it was
+/// submitted, the outcome, and the answer. Blank lines and lines
+/// starting with `#` are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Guesses {
+    entries: Vec<Guess>,
+}
+
+impl Guesses {
+    /// Reads the guesses file. A missing file has no guesses in it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GuessesError> {
+        use guesses_error::*;
+
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse().context(MalformedSnafu { path }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context(ReadSnafu { path }),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GuessesError> {
+        use guesses_error::*;
+
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).context(WriteSnafu { path })
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.entries.push(guess);
+    }
+
+    /// Why `answer` should not be submitted at time `now`, if there is
+    /// a reason.
+    pub fn check(&self, hash: InputHash, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        use refusal::*;
+
+        ensure!(!answer.contains('\n'), MultiLineSnafu);
+
+        let guesses = self
+            .entries
+            .iter()
+            .filter(|g| g.hash == hash && g.part == part);
+
+        let value = answer.parse::<i128>().ok();
+        let mut ready_at = 0;
+
+        for g in guesses {
+            let g_value = g.answer.parse::<i128>().ok();
+
+            match g.outcome {
+                Outcome::Correct => {
+                    return AlreadySolvedSnafu { answer: &g.answer }.fail();
+                }
+                outcome if outcome.is_incorrect() && g.answer == answer => {
+                    return KnownWrongSnafu { outcome }.fail();
+                }
+                Outcome::TooHigh => {
+                    if let (Some(v), Some(bound)) = (value, g_value) {
+                        ensure!(v < bound, AboveBoundSnafu { bound });
+                    }
+                }
+                Outcome::TooLow => {
+                    if let (Some(v), Some(bound)) = (value, g_value) {
+                        ensure!(v > bound, BelowBoundSnafu { bound });
+                    }
+                }
+                _ => {}
+            }
+
+            let delay = match g.outcome {
+                Outcome::Wait { seconds } => seconds,
+                outcome if outcome.is_incorrect() => WRONG_ANSWER_DELAY_S,
+                _ => 0,
+            };
+            ready_at = ready_at.max(g.at + delay);
+        }
+
+        ensure!(
+            now >= ready_at,
+            TooSoonSnafu {
+                seconds: ready_at - now
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl FromStr for Guesses {
+    type Err = ParseGuessesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use parse_guesses_error::*;
+
+        let mut entries = Vec::new();
+
+        for (line, l) in (1_usize..).zip(s.lines()) {
+            if l.trim().is_empty() || l.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = l.splitn(5, ' ');
+            let mut field = || fields.next().context(FieldMissingSnafu { line });
+            let (hash, part, at, outcome, answer) =
+                (field()?, field()?, field()?, field()?, field()?);
+
+            entries.push(Guess {
+                hash: hash.parse().ok().context(FieldInvalidSnafu { line })?,
+                part: part.parse().ok().context(FieldInvalidSnafu { line })?,
+                at: at.parse().ok().context(FieldInvalidSnafu { line })?,
+                outcome: outcome.parse().ok().context(FieldInvalidSnafu { line })?,
+                answer: answer.to_owned(),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl fmt::Display for Guesses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for g in &self.entries {
+            let Guess {
+                hash,
+                part,
+                at,
+                outcome,
+                answer,
+            } = g;
+            writeln!(f, "{hash} {part} {at} {outcome} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An answer to submit for one part of a day.
+#[derive(Debug, Clone)]
+pub struct Submission<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub hash: InputHash,
+    pub answer: &'a str,
+}
+
+/// Submits the answer unless the guesses recorded at `guesses_path`
+/// show that it cannot be right, then records the outcome. A correct
+/// answer is also recorded as the confirmed answer.
+pub fn submit(
+    site: SiteArgs,
+    guesses_path: &Path,
+    answers_path: &Path,
+    submission: Submission<'_>,
+    now: u64,
+) -> Result<Outcome, SubmitError> {
+    use submit_error::*;
+
+    let Submission {
+        day,
+        part,
+        hash,
+        answer,
+    } = submission;
+
+    let mut guesses = Guesses::load(guesses_path).context(GuessesSnafu)?;
+    guesses
+        .check(hash, part, answer, now)
+        .context(RefusedSnafu)?;
+
+    let site = Site::new(site).context(SiteSnafu)?;
+    let outcome = site.submit(day, part, answer).context(SiteSnafu)?;
+
+    guesses.record(Guess {
+        hash,
+        part,
+        at: now,
+        outcome,
+        answer: answer.to_owned(),
+    });
+    guesses.save(guesses_path).context(GuessesSnafu)?;
+
+    if outcome == Outcome::Correct {
+        let mut answers = Answers::load(answers_path).context(AnswersSnafu)?;
+        answers.record(hash, part, answer);
+        answers.save(answers_path).context(AnswersSnafu)?;
+    }
+
+    Ok(outcome)
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum Refusal {
+    #[snafu(display("Multi-line answers must be read and submitted by hand"))]
+    MultiLine,
+
+    #[snafu(display("This part was already solved with {answer}"))]
+    AlreadySolved { answer: String },
+
+    #[snafu(display("This answer was already submitted and was {outcome}"))]
+    KnownWrong { outcome: Outcome },
+
+    #[snafu(display("{bound} was already too high"))]
+    AboveBound { bound: i128 },
+
+    #[snafu(display("{bound} was already too low"))]
+    BelowBound { bound: i128 },
+
+    #[snafu(display("The website will not accept another answer for {seconds}s"))]
+    TooSoon { seconds: u64 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum GuessesError {
+    #[snafu(display("Could not read the guesses from {}", path.display()))]
+    Read { source: io::Error, path: PathBuf },
+
+    #[snafu(display("The guesses in {} are malformed", path.display()))]
+    Malformed {
+        source: ParseGuessesError,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Could not write the guesses to {}", path.display()))]
+    Write { source: io::Error, path: PathBuf },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseGuessesError {
+    #[snafu(display("Line {line} is missing a field"))]
+    FieldMissing { line: usize },
+
+    #[snafu(display("Line {line} has an invalid field"))]
+    FieldInvalid { line: usize },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum SubmitError {
+    #[snafu(display("The answer was not submitted"))]
+    Refused { source: Refusal },
+
+    #[snafu(display("Could not update the recorded guesses"))]
+    Guesses { source: GuessesError },
+
+    #[snafu(display("Could not submit the answer"))]
+    Site { source: SiteError },
+
+    #[snafu(display("Could not record the correct answer"))]
+    Answers { source: AnswersError },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_server::{TempDir, TestServer};
+
+    fn hash() -> InputHash {
+        "1234".parse().unwrap()
+    }
+
+    fn guess(at: u64, outcome: Outcome, answer: &str) -> Guess {
+        Guess {
+            hash: hash(),
+            part: 1,
+            at,
+            outcome,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn responses() {
+        let page = |s| format!("<main><article><p>{s}</p></article></main>");
+
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck...",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently. You have 1m 23s left to wait.",
+                Outcome::Wait { seconds: 83 },
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Outcome::WrongLevel,
+            ),
+        ];
+
+        for (text, outcome) in cases {
+            assert_eq!(Some(outcome), Outcome::from_response(&page(text)), "{text}");
+        }
+        assert_eq!(None, Outcome::from_response(&page("Something else")));
+    }
+
+    #[test]
+    fn guesses_round_trip() {
+        let mut guesses = Guesses::default();
+        guesses.record(guess(100, Outcome::TooHigh, "875"));
+        guesses.record(guess(200, Outcome::Wait { seconds: 30 }, "700"));
+        guesses.record(guess(300, Outcome::Correct, "ABC DEF"));
+
+        let s = guesses.to_string();
+        assert_eq!(
+            "0000000000001234 1 100 too-high 875\n\
+             0000000000001234 1 200 wait-30s 700\n\
+             0000000000001234 1 300 correct ABC DEF\n",
+            s,
+        );
+        assert_eq!(guesses, s.parse().unwrap());
+    }
+
+    #[test]
+    fn refusals() {
+        let mut guesses = Guesses::default();
+        guesses.record(guess(100, Outcome::TooHigh, "875"));
+        guesses.record(guess(200, Outcome::TooLow, "300"));
+        guesses.record(guess(300, Outcome::Wrong, "500"));
+
+        let check = |answer, now| guesses.check(hash(), 1, answer, now);
+
+        assert!(matches!(
+            check("900", 1000),
+            Err(Refusal::AboveBound { bound: 875 })
+        ));
+        assert!(matches!(
+            check("875", 1000),
+            Err(Refusal::KnownWrong { .. })
+        ));
+        assert!(matches!(
+            check("12", 1000),
+            Err(Refusal::BelowBound { bound: 300 })
+        ));
+        assert!(matches!(
+            check("500", 1000),
+            Err(Refusal::KnownWrong { .. })
+        ));
+        assert!(matches!(check("1\n2", 1000), Err(Refusal::MultiLine)));
+        assert!(matches!(
+            check("600", 330),
+            Err(Refusal::TooSoon { seconds: 30 })
+        ));
+        assert!(check("600", 1000).is_ok());
+        assert!(check("ABC", 1000).is_ok());
+
+        // Other parts and inputs are unaffected
+        assert!(guesses.check(hash(), 2, "875", 1000).is_ok());
+
+        guesses.record(guess(400, Outcome::Correct, "600"));
+        assert!(matches!(
+            guesses.check(hash(), 1, "601", 1000),
+            Err(Refusal::AlreadySolved { .. })
+        ));
+    }
+
+    #[test]
+    fn waits_are_respected() {
+        let mut guesses = Guesses::default();
+        guesses.record(guess(100, Outcome::Wait { seconds: 500 }, "1"));
+
+        assert!(matches!(
+            guesses.check(hash(), 1, "1", 150),
+            Err(Refusal::TooSoon { seconds: 450 })
+        ));
+        assert!(guesses.check(hash(), 1, "1", 600).is_ok());
+    }
+
+    fn site(server: &TestServer) -> SiteArgs {
+        SiteArgs {
+            session: Some("abc123".into()),
+            base_url: server.base_url().into(),
+        }
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let server = TestServer::start([
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let dir = TempDir::new("submit");
+        let guesses_path = dir.path().join("guesses");
+        let answers_path = dir.path().join("answers");
+
+        let submission = |answer| Submission {
+            day: 12,
+            part: 1,
+            hash: hash(),
+            answer,
+        };
+        let submit = |answer, now| {
+            submit(
+                site(&server),
+                &guesses_path,
+                &answers_path,
+                submission(answer),
+                now,
+            )
+        };
+
+        assert_eq!(Outcome::TooHigh, submit("875", 1000).unwrap());
+        assert!(matches!(
+            submit("880", 2000),
+            Err(SubmitError::Refused {
+                source: Refusal::AboveBound { bound: 875 }
+            })
+        ));
+        assert_eq!(Outcome::Correct, submit("391", 2000).unwrap());
+
+        let guesses = Guesses::load(&guesses_path).unwrap();
+        assert_eq!(
+            vec![
+                guess(1000, Outcome::TooHigh, "875"),
+                guess(2000, Outcome::Correct, "391"),
+            ],
+            guesses.entries,
+        );
+        let answers = Answers::load(&answers_path).unwrap();
+        assert_eq!(Some("391"), answers.get(hash(), 1));
+
+        let requests = server.finish();
+        assert_eq!(2, requests.len());
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/day/12/answer", requests[0].url);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+        assert_eq!("level=1&answer=875", requests[0].body);
+    }
+
+    #[test]
+    fn unrecognized_responses_are_errors() {
+        let server = TestServer::start([(200, "<p>Please log in</p>")]);
+        let dir = TempDir::new("submit-unrecognized");
+
+        let submission = Submission {
+            day: 1,
+            part: 2,
+            hash: hash(),
+            answer: "42",
+        };
+        let result = submit(
+            site(&server),
+            &dir.path().join("guesses"),
+            &dir.path().join("answers"),
+            submission,
+            0,
+        );
+
+        assert!(matches!(
+            result,
+            Err(SubmitError::Site {
+                source: SiteError::ResponseUnrecognized { .. }
+            })
+        ));
+        assert!(!dir.path().join("guesses").exists());
+
+        server.finish();
+    }
+}
@@ -0,0 +1,116 @@
+//! A local stand-in for the website, for testing the client against.
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+/// What the server was sent.
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| &**v)
+    }
+}
+
+/// Answers requests with the given statuses and bodies, in order.
+/// Any further requests are answered with a 500.
+pub struct TestServer {
+    server: Arc<tiny_http::Server>,
+    base_url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+    thread: JoinHandle<()>,
+}
+
+impl TestServer {
+    pub fn start<const N: usize>(responses: [(u16, &'static str); N]) -> Self {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("Could not start the server");
+        let server = Arc::new(server);
+        let address = server.server_addr().to_ip().expect("Not listening on IP");
+        let base_url = format!("http://{address}");
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut responses = VecDeque::from(responses);
+
+        let thread = thread::spawn({
+            let server = server.clone();
+            let requests = requests.clone();
+            move || {
+                while let Ok(mut request) = server.recv() {
+                    let mut body = String::new();
+                    request
+                        .as_reader()
+                        .read_to_string(&mut body)
+                        .expect("Could not read the request");
+
+                    requests.lock().unwrap().push(Request {
+                        method: request.method().to_string(),
+                        url: request.url().to_owned(),
+                        headers: request
+                            .headers()
+                            .iter()
+                            .map(|h| (h.field.to_string(), h.value.to_string()))
+                            .collect(),
+                        body,
+                    });
+
+                    let (status, body) = responses.pop_front().unwrap_or((500, "Unexpected"));
+                    let response = tiny_http::Response::from_string(body).with_status_code(status);
+                    let _ = request.respond(response);
+                }
+            }
+        });
+
+        Self {
+            server,
+            base_url,
+            requests,
+            thread,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Stops the server, returning every request it received.
+    pub fn finish(self) -> Vec<Request> {
+        self.server.unblock();
+        self.thread.join().expect("The server panicked");
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+/// A directory that is removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("Could not create the directory");
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
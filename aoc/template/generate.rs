@@ -0,0 +1,10 @@
+use crate::Day{{day}};
+use aoc_common::generate::{Generate, GenerateRng};
+
+/// `size` is the number of lines.
+impl Generate for Day{{day}} {
+    fn generate(_rng: &mut GenerateRng, _size: usize) -> String {
+        // Replace with random input once the format is known
+        String::new()
+    }
+}
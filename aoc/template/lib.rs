@@ -0,0 +1,69 @@
+//! Day {{number}}
+//!
+//! Describe the input and what each part finds.
+
+use aoc_common::Solution;
+use snafu::prelude::*;
+
+mod generate;
+
+/// The parsed input is each line.
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    const DAY: u8 = {{number}};
+
+    type Input<'a> = Vec<&'a str>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        Ok(s.lines().collect())
+    }
+
+    fn part1(_lines: &Self::Input<'_>, (): ()) -> Result<usize> {
+        NotSolvedSnafu { part: 1_u8 }.fail()
+    }
+
+    fn part2(_lines: &Self::Input<'_>, (): ()) -> Result<usize> {
+        NotSolvedSnafu { part: 2_u8 }.fail()
+    }
+}
+
+pub fn part1(s: &str) -> Result<usize> {
+    Day{{day}}::part1(&Day{{day}}::parse(s)?, ())
+}
+
+pub fn part2(s: &str) -> Result<usize> {
+    Day{{day}}::part2(&Day{{day}}::parse(s)?, ())
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Part {part} has not been solved yet"))]
+    NotSolved { part: u8 },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        Day{{day}}::parse(INPUT)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use aoc_common::{DayArgs, Solution};
+use day_{{day}}::{part1, part2, Day{{day}}};
+use snafu::prelude::*;
+
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
+
+#[snafu::report]
+fn main() -> Result<()> {
+    let args = DayArgs::from_env();
+    let result = run(&args);
+    args.report_failure(Day{{day}}::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
+}
+
+fn run(args: &DayArgs) -> Result<()> {
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
+    let mut output = args
+        .output(Day{{day}}::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
+
+    output.solve(1, || part1(&input))?;
+
+    output.solve(2, || part2(&input))?;
+
+    output.finish().context(AnswersSnafu)?;
+
+    Ok(())
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not read the input"))]
+    Input {
+        source: aoc_common::InputError,
+    },
+
+    #[snafu(display("Could not check the answers"))]
+    Answers {
+        source: aoc_common::AnswersError,
+    },
+
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle {
+        source: day_{{day}}::Error,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
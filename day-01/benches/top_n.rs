@@ -0,0 +1,41 @@
+//! Compares the ways of totalling the elves carrying the most, on
+//! generated inputs with increasingly many elves:
+//!
+//! ```text
+//! cargo bench -p day-01 --bench top_n
+//! ```
+
+use aoc_common::{generate::generate, Alternative, Solution};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_01::{Day01, TOP_N};
+
+/// The seed for all generated inputs, so that runs can be compared.
+const SEED: u64 = 0;
+
+fn top_n(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day-01/top-n");
+    group.sample_size(10);
+
+    for elves in [1_000, 100_000, 1_000_000] {
+        let input = generate::<Day01>(SEED, elves);
+        let parsed = match Day01::parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => panic!("Could not parse {elves} generated elves: {e}"),
+        };
+
+        let heap: Alternative<Day01, _, _> = ("heap", Day01::part2);
+        for (name, part2) in std::iter::once(heap).chain(Day01::part2_alternatives()) {
+            for n in [TOP_N, 100] {
+                let id = BenchmarkId::new(format!("{name}-{n}"), elves);
+                group.bench_with_input(id, &parsed, |b, parsed| {
+                    b.iter(|| part2(black_box(parsed), n))
+                });
+            }
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, top_n);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+use crate::Day01;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::fmt::Write;
+
+/// `size` is the number of elves, of which there is at least one.
+impl Generate for Day01 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for elf in 0..size.max(1) {
+            if elf != 0 {
+                s.push('\n');
+            }
+
+            for _ in 0..rng.gen_range(1..=15) {
+                let calories: u32 = rng.gen_range(1000..=70000);
+                writeln!(s, "{calories}").expect("Unable to write to a string");
+            }
+        }
+
+        s
+    }
+}
@@ -0,0 +1,569 @@
+//! Day 1: Calorie Counting
+//!
+//! The input lists the calories of each snack an elf carries, one per
+//! line, with a blank line between elves. Part 1 finds the most
+//! calories carried by one elf; part 2 totals the [`TOP_N`] elves
+//! carrying the most.
+//!
+//! The [`leaderboard`] ranks the elves, saying which carried what,
+//! and the [`statistics`] describe how the loads are spread. Inputs
+//! written by hand can be read in the [`Dialect::Tolerant`] dialect.
+
+use aoc_common::{input::numbered_lines, Alternative, LocateExt, Located, Solution, WithinExt};
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead, num::ParseIntError};
+
+mod generate;
+mod statistics;
+
+pub use statistics::{Distribution, Histogram, Statistics, MAX_BUCKETS};
+
+/// How many elves part 2 totals.
+pub const TOP_N: usize = 3;
+
+/// The parsed input is each [`Elf`], in input order.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<Elf<'a>>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = u64;
+
+    type Part2Params = usize;
+    type Part2 = u64;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = TOP_N;
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_with(s, Dialect::Strict)
+    }
+
+    fn part1(elves: &Self::Input<'_>, (): ()) -> Result<u64> {
+        elves.iter().map(|e| e.total).max().context(NoNumbersSnafu)
+    }
+
+    fn part2(elves: &Self::Input<'_>, n: usize) -> Result<u64> {
+        ensure_enough_elves(n, elves.len())?;
+
+        largest_n(elves.iter().map(|e| e.total), n)
+            .into_iter()
+            .try_fold(0, u64::checked_add)
+            .context(OverflowSnafu)
+    }
+
+    fn part2_alternatives() -> Vec<Alternative<Self, usize, u64>> {
+        vec![("sort", |elves: &Vec<Elf<'_>>, n| part2_sort(elves, n))]
+    }
+}
+
+/// Sorts every total, only to keep the first `n`.
+fn part2_sort(elves: &[Elf], n: usize) -> Result<u64> {
+    ensure_enough_elves(n, elves.len())?;
+
+    elves
+        .iter()
+        .map(|e| e.total)
+        .sorted_unstable_by(|l, r| l.cmp(r).reverse())
+        .take(n)
+        .try_fold(0, u64::checked_add)
+        .context(OverflowSnafu)
+}
+
+/// The `n` largest items, largest first.
+///
+/// Only `n` items are kept at a time, in a heap with the smallest on
+/// top, so selecting from `m` items takes O(m log n) time.
+pub fn largest_n<T: Ord>(items: impl IntoIterator<Item = T>, n: usize) -> Vec<T> {
+    let mut heap = BinaryHeap::with_capacity(n);
+
+    for item in items {
+        if heap.len() < n {
+            heap.push(Reverse(item));
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            // The heap is restored once `smallest` is dropped
+            if item > smallest.0 {
+                *smallest = Reverse(item);
+            }
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(t)| t)
+        .collect()
+}
+
+/// How closely the input must follow the puzzle's format.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// One number per line, with a single empty line between elves.
+    #[default]
+    Strict,
+
+    /// Also allows:
+    ///
+    /// - carriage returns and whitespace around each line
+    /// - whitespace on the lines between elves, and more than one
+    ///   such line in a row
+    /// - comments from a `#` to the end of the line
+    /// - naming the elf with a line like `elf: Bob`
+    Tolerant,
+}
+
+/// Parses the input in the given dialect.
+pub fn parse_with(s: &str, dialect: Dialect) -> Result<Vec<Elf<'_>>> {
+    match dialect {
+        Dialect::Strict => {
+            let lines = s.lines().map(|l| parse_line(s, l).context(BadNumberSnafu));
+            summed_calories_by_elf(lines.map(|l| l.map(Line::from)), dialect).collect()
+        }
+        Dialect::Tolerant => {
+            let lines = s.lines().map(|l| parse_tolerant_line(s, l));
+            summed_calories_by_elf(lines, dialect).collect()
+        }
+    }
+}
+
+/// The snacks one elf carries.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Elf<'a> {
+    /// The elf's name, if the input gives one.
+    pub label: Option<&'a str>,
+    /// The calories of every snack combined.
+    pub total: u64,
+    /// How many snacks there are.
+    pub items: usize,
+}
+
+/// One elf's place on the [`leaderboard`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Standing<'a> {
+    /// Elves carrying the same total share a rank, and the following
+    /// rank is skipped for each extra elf: 1, 2, 2, 4.
+    pub rank: usize,
+    /// Where the elf appears in the input, starting from 0.
+    pub elf_index: usize,
+    pub label: Option<&'a str>,
+    pub total: u64,
+    pub item_count: usize,
+}
+
+/// The most calories carried by any one elf.
+pub fn max_calories(s: &str) -> Result<u64> {
+    Day01::part1(&Day01::parse(s)?, ())
+}
+
+/// The combined calories of the `n` elves carrying the most.
+pub fn max_n_calories(s: &str, n: usize) -> Result<u64> {
+    Day01::part2(&Day01::parse(s)?, n)
+}
+
+/// The `n` elves carrying the most, from most to least. Tied elves are
+/// listed in input order, and a tie at the end of the leaderboard is
+/// decided the same way.
+pub fn leaderboard(s: &str, n: usize) -> Result<Vec<Standing<'_>>> {
+    leaderboard_of(&Day01::parse(s)?, n)
+}
+
+/// As [`leaderboard`], for elves that have already been parsed.
+pub fn leaderboard_of<'a>(elves: &[Elf<'a>], n: usize) -> Result<Vec<Standing<'a>>> {
+    ensure_enough_elves(n, elves.len())?;
+
+    // Among equal totals, the earlier elf is the larger key
+    let keys = elves.iter().enumerate().map(|(i, e)| (e.total, Reverse(i)));
+    let order = largest_n(keys, n).into_iter().map(|(_, Reverse(i))| i);
+
+    let mut standings = Vec::<Standing>::with_capacity(n);
+
+    for (position, elf_index) in order.enumerate() {
+        let Elf {
+            label,
+            total,
+            items,
+        } = elves[elf_index];
+        let rank = match standings.last() {
+            Some(previous) if previous.total == total => previous.rank,
+            _ => position + 1,
+        };
+
+        standings.push(Standing {
+            rank,
+            elf_index,
+            label,
+            total,
+            item_count: items,
+        });
+    }
+
+    Ok(standings)
+}
+
+fn ensure_enough_elves(wanted: usize, found: usize) -> Result<()> {
+    ensure!(wanted <= found, NotEnoughElvesSnafu { wanted, found });
+    Ok(())
+}
+
+/// The spread of the elves' calories and item counts.
+pub fn statistics(s: &str) -> Result<Statistics> {
+    Statistics::new(&Day01::parse(s)?)
+}
+
+/// As [`max_calories`], reading one line at a time.
+pub fn max_calories_from_reader(reader: impl BufRead) -> Result<u64> {
+    itertools::process_results(totals_from_reader(reader), |totals| totals.max())?
+        .context(NoNumbersSnafu)
+}
+
+/// As [`max_n_calories`], reading one line at a time and only
+/// remembering the `n` largest totals.
+pub fn max_n_calories_from_reader(reader: impl BufRead, n: usize) -> Result<u64> {
+    let mut found = 0;
+    let largest = itertools::process_results(totals_from_reader(reader), |totals| {
+        largest_n(totals.inspect(|_| found += 1), n)
+    })?;
+
+    ensure_enough_elves(n, found)?;
+
+    largest
+        .into_iter()
+        .try_fold(0, u64::checked_add)
+        .context(OverflowSnafu)
+}
+
+fn totals_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<u64>> {
+    let lines = numbered_lines(reader).map(|(n, l)| {
+        let l = l.context(ReadSnafu)?;
+        let line = parse_line(&l, &l).on_line(n).context(BadNumberSnafu)?;
+        Ok(Line::from(line))
+    });
+    summed_calories_by_elf(lines, Dialect::Strict).map(|elf| elf.map(|e| e.total))
+}
+
+/// What one line of the input holds.
+enum Line<'a> {
+    Calories(u32),
+    Separator,
+    Label(&'a str),
+    Comment,
+}
+
+impl From<Option<u32>> for Line<'_> {
+    fn from(calories: Option<u32>) -> Self {
+        calories.map_or(Line::Separator, Line::Calories)
+    }
+}
+
+/// A blank line separates one elf's snacks from the next.
+fn parse_line(s: &str, l: &str) -> Result<Option<u32>, Located<ParseIntError>> {
+    if l.is_empty() {
+        return Ok(None);
+    }
+
+    l.parse().map(Some).locate(s, l)
+}
+
+/// Everything but a number, a name or a comment separates elves.
+fn parse_tolerant_line<'a>(s: &'a str, l: &'a str) -> Result<Line<'a>> {
+    let (content, comment) = match l.split_once('#') {
+        Some((content, _)) => (content, true),
+        None => (l, false),
+    };
+    let content = content.trim();
+
+    if content.is_empty() {
+        return Ok(if comment {
+            Line::Comment
+        } else {
+            Line::Separator
+        });
+    }
+
+    if let Some(name) = content.strip_prefix("elf:") {
+        let name = name.trim();
+        if name.is_empty() {
+            return NameMissingSnafu
+                .fail()
+                .locate(s, content)
+                .context(LabelEmptySnafu);
+        }
+        return Ok(Line::Label(name));
+    }
+
+    content
+        .parse()
+        .map(Line::Calories)
+        .locate(s, content)
+        .context(BadNumberSnafu)
+}
+
+fn summed_calories_by_elf<'a>(
+    mut lines: impl Iterator<Item = Result<Line<'a>>>,
+    dialect: Dialect,
+) -> impl Iterator<Item = Result<Elf<'a>>> {
+    std::iter::from_fn(move || {
+        let mut elf = None;
+
+        for l in lines.by_ref() {
+            match l {
+                Ok(Line::Calories(v)) => {
+                    let Elf { total, items, .. } = elf.get_or_insert_with(Elf::default);
+                    *items += 1;
+                    // Each snack fits in a `u32`, but a total of many may not
+                    *total = match total.checked_add(v.into()).context(OverflowSnafu) {
+                        Ok(v) => v,
+                        Err(e) => return Some(Err(e)),
+                    };
+                }
+                Ok(Line::Label(name)) => {
+                    let Elf { label, .. } = elf.get_or_insert_with(Elf::default);
+                    if let Some(first) = label {
+                        let second = name.to_owned();
+                        let first = first.to_string();
+                        return Some(LabelRepeatedSnafu { first, second }.fail());
+                    }
+                    *label = Some(name);
+                }
+                // Strict inputs end at the first extra separator
+                Ok(Line::Separator) if elf.is_none() && dialect == Dialect::Tolerant => {}
+                Ok(Line::Separator) => break,
+                Ok(Line::Comment) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        elf.map(Ok)
+    })
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The elf's name is missing"))]
+pub struct NameMissingError;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Read {
+        source: std::io::Error,
+    },
+    BadNumber {
+        source: Located<ParseIntError>,
+    },
+    NoNumbers,
+    Overflow,
+
+    #[snafu(display("The elf has no name"))]
+    LabelEmpty {
+        source: Located<NameMissingError>,
+    },
+
+    #[snafu(display("The elf {first:?} was also named {second:?}"))]
+    LabelRepeated {
+        first: String,
+        second: String,
+    },
+
+    #[snafu(display("Wanted the top {wanted} elves, but only {found} were found"))]
+    NotEnoughElves {
+        wanted: usize,
+        found: usize,
+    },
+
+    #[snafu(display("The percentile {percentile} is not between 0 and 100"))]
+    PercentileOutOfRange {
+        percentile: f64,
+    },
+
+    #[snafu(display("The histogram would need {buckets} buckets, but only {max} are allowed"))]
+    TooManyBuckets {
+        buckets: u64,
+        max: u64,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Groups of calories, with the odd `x` among them so that bad
+    /// numbers turn up in any group.
+    const ARBITRARY_INPUT: &str = r"([0-9]{1,6}\n){0,5}(\n(([0-9]{1,6}|x)\n){0,5}){0,5}";
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(max_calories(INPUT)?, 24000);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(max_n_calories(INPUT, TOP_N)?, 45000);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_leaderboard() -> Result<()> {
+        let standing = |rank, elf_index, total, item_count| Standing {
+            rank,
+            elf_index,
+            label: None,
+            total,
+            item_count,
+        };
+
+        assert_eq!(
+            leaderboard(INPUT, 3)?,
+            [
+                standing(1, 3, 24000, 3),
+                standing(2, 2, 11000, 2),
+                standing(3, 4, 10000, 1),
+            ],
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ties_share_a_rank() -> Result<()> {
+        let board = leaderboard("5\n\n7\n\n2\n3\n\n7\n\n1\n", 4)?;
+        let places = board
+            .iter()
+            .map(|s| (s.rank, s.elf_index))
+            .collect::<Vec<_>>();
+
+        assert_eq!(places, [(1, 1), (1, 3), (3, 0), (3, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn not_enough_elves() {
+        let expected = "NotEnoughElves { wanted: 6, found: 5 }";
+        assert_eq!(
+            format!("{:?}", leaderboard(INPUT, 6).unwrap_err()),
+            expected
+        );
+        assert_eq!(
+            format!("{:?}", max_n_calories(INPUT, 6).unwrap_err()),
+            expected
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                max_n_calories_from_reader(INPUT.as_bytes(), 6).unwrap_err()
+            ),
+            expected,
+        );
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day01>(5, 20)
+    }
+
+    #[test]
+    #[snafu::report]
+    fn tolerant_dialect() -> Result<()> {
+        let input = "# Packed on the first day\r\n\
+                     elf: Bob\r\n\
+                     1000\r\n\
+                     \x20 2000  # a big one\r\n\
+                     \r\n\
+                     \x20\t\r\n\
+                     \r\n\
+                     4000\r\n\
+                     \r\n\
+                     elf:  Alice Smith \r\n\
+                     # Nothing yet\r\n\
+                     5000\r\n";
+        let elves = parse_with(input, Dialect::Tolerant)?;
+
+        let labels = elves.iter().map(|e| e.label).collect::<Vec<_>>();
+        assert_eq!(labels, [Some("Bob"), None, Some("Alice Smith")]);
+        let totals = elves.iter().map(|e| e.total).collect::<Vec<_>>();
+        assert_eq!(totals, [3000, 4000, 5000]);
+
+        let board = leaderboard_of(&elves, 2)?;
+        assert_eq!(board[0].label, Some("Alice Smith"));
+        assert_eq!(board[1].label, None);
+        Ok(())
+    }
+
+    #[test]
+    fn tolerant_dialect_errors() {
+        let e = parse_with("1000\nelf:\n", Dialect::Tolerant).unwrap_err();
+        assert!(matches!(e, Error::LabelEmpty { .. }), "{e:?}");
+
+        let e = parse_with("elf: A\n1\nelf: B\n", Dialect::Tolerant).unwrap_err();
+        assert!(matches!(e, Error::LabelRepeated { .. }), "{e:?}");
+
+        let e = parse_with("elf: A\n", Dialect::Strict).unwrap_err();
+        assert!(matches!(e, Error::BadNumber { .. }), "{e:?}");
+    }
+
+    #[test]
+    fn largest_n_of_any_size() {
+        let items = [5, 1, 9, 3, 9, 7];
+
+        assert_eq!(largest_n(items, 0), [0; 0]);
+        assert_eq!(largest_n(items, 3), [9, 9, 7]);
+        assert_eq!(largest_n(items, 10), [9, 9, 7, 5, 3, 1]);
+    }
+
+    proptest! {
+        #[test]
+        fn largest_n_matches_sorting(items: Vec<u32>, n in 0_usize..10) {
+            let mut sorted = items.clone();
+            sorted.sort_unstable_by(|l, r| r.cmp(l));
+            sorted.truncate(n);
+
+            prop_assert_eq!(largest_n(items, n), sorted);
+        }
+
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day01::parse(&s) {
+                let _ = Day01::part1(&parsed, Day01::PART1_PARAMS);
+                let _ = Day01::part2(&parsed, Day01::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn tolerant_accepts_single_separators(s in r"([0-9]{1,6}\n){1,5}(\n([0-9]{1,6}\n){1,5}){0,5}") {
+            let strict = format!("{:?}", parse_with(&s, Dialect::Strict));
+            let tolerant = format!("{:?}", parse_with(&s, Dialect::Tolerant));
+            prop_assert_eq!(strict, tolerant);
+        }
+
+        #[test]
+        fn tolerant_never_panics(s in r"((elf: ?[a-z]{0,3}|[0-9]{1,6}|x| ?# ?[a-z]{0,3}| \t?)\r?\n){0,20}") {
+            if let Ok(parsed) = parse_with(&s, Dialect::Tolerant) {
+                let _ = leaderboard_of(&parsed, 2);
+                let _ = Statistics::new(&parsed);
+            }
+        }
+
+        #[test]
+        fn reader_matches_str(s in ARBITRARY_INPUT) {
+            // Down to the line that a bad number is reported on
+            let from_str = (max_calories(&s), max_n_calories(&s, TOP_N));
+            let from_reader = (
+                max_calories_from_reader(s.as_bytes()),
+                max_n_calories_from_reader(s.as_bytes(), TOP_N),
+            );
+            prop_assert_eq!(format!("{from_str:?}"), format!("{from_reader:?}"));
+        }
+    }
+}
@@ -1,85 +1,164 @@
-use itertools::Itertools;
+use aoc_common::{DayArgs, Solution};
+use clap::Parser;
+use day_01::{
+    leaderboard_of, parse_with, Day01, Dialect, Distribution, Standing, Statistics, TOP_N,
+};
 use snafu::prelude::*;
-use std::num::ParseIntError;
-
-const INPUT: &str = include_str!("../input");
-const TOP_N: usize = 3;
+use std::num::NonZeroU64;
+
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
+
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Accept comments, named elves, and looser blank lines in the
+    /// input
+    #[arg(long)]
+    tolerant: bool,
+
+    /// Print a table of the `N` elves carrying the most instead of
+    /// solving the puzzle
+    #[arg(long, value_name = "N")]
+    leaderboard: Option<usize>,
+
+    /// Print statistics of the elves' loads instead of solving the
+    /// puzzle
+    #[arg(long)]
+    stats: bool,
+
+    /// The percentiles that `--stats` prints
+    #[arg(long, value_name = "P", num_args = 1.., default_values_t = [25.0, 75.0, 90.0])]
+    percentile: Vec<f64>,
+
+    /// Print a histogram of the elves' total calories, with buckets
+    /// of this width, instead of solving the puzzle
+    #[arg(long, value_name = "WIDTH")]
+    histogram: Option<NonZeroU64>,
+}
 
 #[snafu::report]
 fn main() -> Result<()> {
-    let part1 = max_calories(INPUT)?;
-    println!("{part1}");
-    assert_eq!(part1, 70374);
+    let args = Args::parse();
+    let result = run(&args);
+    args.day
+        .report_failure(Day01::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
+}
 
-    let part2 = max_n_calories(INPUT, TOP_N)?;
-    println!("{part2}");
-    assert_eq!(part2, 204610);
+fn run(args: &Args) -> Result<()> {
+    let &Args {
+        day: ref args,
+        tolerant,
+        leaderboard,
+        stats,
+        ref percentile,
+        histogram,
+    } = args;
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
+    let dialect = if tolerant {
+        Dialect::Tolerant
+    } else {
+        Dialect::Strict
+    };
+
+    if leaderboard.is_some() || stats || histogram.is_some() {
+        let elves = parse_with(&input, dialect)?;
+
+        if let Some(n) = leaderboard {
+            print_leaderboard(&leaderboard_of(&elves, n)?);
+        }
 
-    Ok(())
-}
+        if stats || histogram.is_some() {
+            let statistics = Statistics::new(&elves)?;
+            if stats {
+                print_statistics(&statistics, percentile)?;
+            }
+            if let Some(width) = histogram {
+                print!("{}", statistics.calories.histogram(width)?);
+            }
+        }
 
-fn max_calories(s: &str) -> Result<u32> {
-    itertools::process_results(summed_calories_by_elf(s), |i| {
-        i.max().context(NoNumbersSnafu)
-    })?
-}
+        return Ok(());
+    }
 
-fn max_n_calories(s: &str, n: usize) -> Result<u32> {
-    itertools::process_results(summed_calories_by_elf(s), |i| {
-        i.sorted_unstable_by(|l, r| l.cmp(r).reverse())
-            .take(n) // What if there wasn't N?
-            .sum()
-    })
-}
+    let mut output = args
+        .output(Day01::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
 
-fn summed_calories_by_elf(s: &str) -> impl Iterator<Item = Result<u32>> + '_ {
-    let mut lines = s.lines();
+    output.solve(1, || Day01::part1(&parse_with(&input, dialect)?, ()))?;
 
-    std::iter::from_fn(move || {
-        let mut sum = None;
+    output.solve(2, || Day01::part2(&parse_with(&input, dialect)?, TOP_N))?;
 
-        while let Some(l) = lines.next() {
-            if l.is_empty() {
-                break;
-            }
+    output.finish().context(AnswersSnafu)?;
 
-            let v = match l.parse::<u32>().context(BadNumberSnafu) {
-                Ok(v) => v,
-                Err(e) => return Some(Err(e)),
-            };
+    Ok(())
+}
 
-            *sum.get_or_insert(0) += v;
+/// Elves are numbered from 1, as the puzzle does. Names are only
+/// shown when the input gives some.
+fn print_leaderboard(standings: &[Standing<'_>]) {
+    let named = standings.iter().any(|s| s.label.is_some());
+
+    let header = ["Rank", "Elf", "Name", "Calories", "Items"].map(String::from);
+    let rows = standings.iter().map(|s| {
+        [
+            s.rank.to_string(),
+            (s.elf_index + 1).to_string(),
+            s.label.unwrap_or("-").to_owned(),
+            s.total.to_string(),
+            s.item_count.to_string(),
+        ]
+    });
+    let rows = std::iter::once(header).chain(rows).collect::<Vec<_>>();
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = usize::max(*width, cell.chars().count());
         }
+    }
 
-        sum.map(Ok)
-    })
+    for [rank, elf, name, total, items] in &rows {
+        let [w0, w1, w2, w3, w4] = widths;
+        let name = if named {
+            format!("  {name:<w2$}")
+        } else {
+            String::new()
+        };
+        println!("{rank:>w0$}  {elf:>w1$}{name}  {total:>w3$}  {items:>w4$}");
+    }
 }
 
-#[derive(Debug, Snafu)]
-enum Error {
-    BadNumber { source: ParseIntError },
-    NoNumbers,
+fn print_statistics(statistics: &Statistics, percentiles: &[f64]) -> Result<()> {
+    println!("Elves: {}", statistics.calories.count());
+    print_distribution("Calories", &statistics.calories, percentiles)?;
+    print_distribution("Items", &statistics.items, percentiles)?;
+    Ok(())
 }
 
-type Result<T, E = Error> = std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
+fn print_distribution(name: &str, d: &Distribution, percentiles: &[f64]) -> Result<()> {
+    println!("{name}:");
+    println!("  min {}, max {}", d.min(), d.max());
+    println!("  mean {:.1}, median {:.1}", d.mean(), d.median());
+    println!("  standard deviation {:.1}", d.standard_deviation());
+    for &p in percentiles {
+        println!("  {p}th percentile {:.1}", d.percentile(p)?);
+    }
+    Ok(())
+}
 
-    const INPUT: &str = include_str!("../input.test");
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-    #[test]
-    #[snafu::report]
-    fn example() -> Result<()> {
-        assert_eq!(max_calories(INPUT)?, 24000);
-        Ok(())
-    }
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    #[test]
-    #[snafu::report]
-    fn example_part2() -> Result<()> {
-        assert_eq!(max_n_calories(INPUT, TOP_N)?, 45000);
-        Ok(())
-    }
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_01::Error },
 }
+
+type Result<T, E = Error> = std::result::Result<T, E>;
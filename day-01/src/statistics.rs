@@ -0,0 +1,237 @@
+use crate::{Elf, Result};
+use snafu::prelude::*;
+use std::{fmt, num::NonZeroU64};
+
+/// A histogram has at most this many buckets.
+pub const MAX_BUCKETS: u64 = 1_000;
+
+/// How the elves' loads are spread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// The total calories carried by each elf.
+    pub calories: Distribution,
+    /// How many snacks each elf carries.
+    pub items: Distribution,
+}
+
+impl Statistics {
+    pub fn new(elves: &[Elf]) -> Result<Self> {
+        let calories = Distribution::new(elves.iter().map(|e| e.total))?;
+        let items = Distribution::new(elves.iter().map(|e| e.items as u64))?;
+
+        Ok(Self { calories, items })
+    }
+}
+
+/// Summary measures of a set of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    // Sorted, and never empty
+    values: Vec<u64>,
+}
+
+impl Distribution {
+    pub fn new(values: impl IntoIterator<Item = u64>) -> Result<Self> {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        ensure!(!values.is_empty(), crate::NoNumbersSnafu);
+        values.sort_unstable();
+
+        Ok(Self { values })
+    }
+
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn min(&self) -> u64 {
+        self.values[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.values[self.values.len() - 1]
+    }
+
+    /// Summed as a `u128`, which no number of `u64`s that fit in
+    /// memory can overflow.
+    pub fn mean(&self) -> f64 {
+        let sum = self.values.iter().map(|&v| u128::from(v)).sum::<u128>();
+        sum as f64 / self.count() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        self.interpolate(0.5)
+    }
+
+    /// The value below which `percentile` percent of the values fall,
+    /// interpolating between the two closest values.
+    pub fn percentile(&self, percentile: f64) -> Result<f64> {
+        ensure!(
+            (0.0..=100.0).contains(&percentile),
+            crate::PercentileOutOfRangeSnafu { percentile }
+        );
+
+        Ok(self.interpolate(percentile / 100.0))
+    }
+
+    /// The population standard deviation.
+    pub fn standard_deviation(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .values
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.count() as f64;
+
+        variance.sqrt()
+    }
+
+    /// Counts the values in buckets of `width`, from the bucket holding
+    /// the smallest value to the one holding the largest.
+    pub fn histogram(&self, width: NonZeroU64) -> Result<Histogram> {
+        let first = self.min() / width;
+        let last = self.max() / width;
+
+        let buckets = last - first + 1;
+        ensure!(
+            buckets <= MAX_BUCKETS,
+            crate::TooManyBucketsSnafu {
+                buckets,
+                max: MAX_BUCKETS
+            }
+        );
+
+        let mut counts = vec![0; buckets as usize];
+        for &v in &self.values {
+            counts[(v / width - first) as usize] += 1;
+        }
+
+        Ok(Histogram {
+            width,
+            start: first * width.get(),
+            counts,
+        })
+    }
+
+    fn interpolate(&self, fraction: f64) -> f64 {
+        let position = fraction * (self.count() - 1) as f64;
+        let below = self.values[position.floor() as usize] as f64;
+        let above = self.values[position.ceil() as usize] as f64;
+
+        below + (above - below) * position.fract()
+    }
+}
+
+/// How many values fall in each bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub width: NonZeroU64,
+    /// The smallest value of the first bucket.
+    pub start: u64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Each bucket's range of values, and how many fall in it.
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, u64, usize)> + '_ {
+        let width = self.width.get();
+
+        (0..).zip(&self.counts).map(move |(i, &count)| {
+            let start = self.start + i * width;
+            (start, start.saturating_add(width - 1), count)
+        })
+    }
+}
+
+/// The longest bar is drawn this many characters long.
+const BAR_WIDTH: usize = 50;
+
+/// One line per bucket, with a bar in proportion to its count.
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let digits = |v: u64| v.to_string().len();
+        let value_width = self.buckets().map(|(_, end, _)| digits(end)).max();
+        let value_width = value_width.unwrap_or(0);
+        let count_width = digits(most as u64);
+
+        for (start, end, count) in self.buckets() {
+            let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most));
+            let line =
+                format!("{start:>value_width$}..={end:<value_width$} {count:>count_width$} {bar}");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Day01, Error};
+    use aoc_common::Solution;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        let statistics = Statistics::new(&Day01::parse(INPUT)?)?;
+        let calories = &statistics.calories;
+
+        assert_eq!(calories.count(), 5);
+        assert_eq!(calories.mean(), 11000.0);
+        assert_eq!(calories.median(), 10000.0);
+        assert_eq!(calories.percentile(25.0)?, 6000.0);
+        assert_eq!(calories.percentile(100.0)?, 24000.0);
+        assert!((calories.standard_deviation() - 6985.700).abs() < 0.001);
+
+        let items = &statistics.items;
+        assert_eq!((items.min(), items.max()), (1, 3));
+        assert_eq!(items.mean(), 2.0);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn percentiles_interpolate() -> Result<()> {
+        let d = Distribution::new([10, 20])?;
+
+        assert_eq!(d.median(), 15.0);
+        assert_eq!(d.percentile(90.0)?, 19.0);
+        assert!(matches!(
+            d.percentile(101.0),
+            Err(Error::PercentileOutOfRange { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn histogram() -> Result<()> {
+        let d = Distribution::new([4000, 6000, 10000, 11000, 24000])?;
+        let histogram = d.histogram(NonZeroU64::new(5000).unwrap())?;
+
+        assert_eq!(histogram.start, 0);
+        assert_eq!(histogram.counts, [1, 1, 2, 0, 1]);
+        let lines = [
+            "    0..=4999  1 #########################",
+            " 5000..=9999  1 #########################",
+            "10000..=14999 2 ##################################################",
+            "15000..=19999 0",
+            "20000..=24999 1 #########################",
+        ];
+        assert_eq!(histogram.to_string().lines().collect::<Vec<_>>(), lines);
+
+        let too_narrow = d.histogram(NonZeroU64::new(1).unwrap());
+        assert!(matches!(too_narrow, Err(Error::TooManyBuckets { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn large_totals_do_not_overflow() {
+        let d = Distribution::new([u64::MAX, u64::MAX]).unwrap();
+        assert_eq!(d.mean(), u64::MAX as f64);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::Day02;
+use aoc_common::generate::{Generate, GenerateRng, SliceRandom};
+
+/// `size` is the number of rounds.
+impl Generate for Day02 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for _ in 0..size {
+            let them = ['A', 'B', 'C'].choose(rng).expect("Not empty");
+            let response = ['X', 'Y', 'Z'].choose(rng).expect("Not empty");
+
+            s.extend([*them, ' ', *response, '\n']);
+        }
+
+        s
+    }
+}
@@ -0,0 +1,303 @@
+//! Day 2: Rock Paper Scissors
+//!
+//! The input is a strategy guide: one round per line, giving the
+//! opponent's shape and a response. Part 1 reads the response as the
+//! shape to play; part 2 reads it as the outcome to aim for. Both
+//! total our score over every round.
+//!
+//! The shapes, who beats whom, and what each shape scores come from
+//! [`Rules`], so guides for games with more shapes can be scored too.
+
+use aoc_common::{
+    diagnostic::end_of, input::numbered_lines, LocateExt, Located, Solution, WithinExt,
+};
+use snafu::prelude::*;
+use std::io::BufRead;
+
+mod generate;
+mod rules;
+
+pub use rules::{
+    Outcome, Response, Round, RuleError, Rules, RulesError, ScoreError, Shape, DRAW, LOSE,
+    ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_LIZARD_SPOCK, WIN,
+};
+
+type Score = u64;
+
+/// The parsed input is each round's opponent shape and response,
+/// along with the rules they are played by.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Guide;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = Score;
+
+    type Part2Params = ();
+    type Part2 = Score;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_with(s, Rules::default())
+    }
+
+    fn part1(guide: &Self::Input<'_>, (): ()) -> Result<Score> {
+        guide.total(Rules::score_as_shape)
+    }
+
+    fn part2(guide: &Self::Input<'_>, (): ()) -> Result<Score> {
+        guide.total(Rules::score_as_outcome)
+    }
+}
+
+/// A strategy guide, read according to its rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub rules: Rules,
+    /// Their shape and our (not yet interpreted) response.
+    pub rounds: Vec<Round>,
+}
+
+impl Guide {
+    fn total(&self, score: fn(&Rules, Round) -> Result<Score, ScoreError>) -> Result<Score> {
+        (1_usize..)
+            .zip(&self.rounds)
+            .try_fold(0, |total: Score, (round, &r)| {
+                let score = score(&self.rules, r).context(ScoreSnafu { round })?;
+                total.checked_add(score).context(OverflowSnafu)
+            })
+    }
+}
+
+/// Reads a guide whose codes come from `rules`.
+pub fn parse_with(s: &str, rules: Rules) -> Result<Guide> {
+    let rounds = s
+        .lines()
+        .map(|l| parse_hand(&rules, s, l).context(HandInvalidSnafu))
+        .collect::<Result<_>>()?;
+
+    Ok(Guide { rules, rounds })
+}
+
+/// Our score when the response is the shape to play.
+pub fn total_score(s: &str) -> Result<Score> {
+    Day02::part1(&Day02::parse(s)?, ())
+}
+
+/// Our score when the response is the outcome to aim for.
+pub fn total_score_part2(s: &str) -> Result<Score> {
+    Day02::part2(&Day02::parse(s)?, ())
+}
+
+/// As [`total_score`], reading one line at a time.
+pub fn total_score_from_reader(reader: impl BufRead) -> Result<Score> {
+    total_from_reader(reader, Rules::score_as_shape)
+}
+
+/// As [`total_score_part2`], reading one line at a time.
+pub fn total_score_part2_from_reader(reader: impl BufRead) -> Result<Score> {
+    total_from_reader(reader, Rules::score_as_outcome)
+}
+
+fn total_from_reader(
+    reader: impl BufRead,
+    score: fn(&Rules, Round) -> Result<Score, ScoreError>,
+) -> Result<Score> {
+    let rules = Rules::default();
+
+    numbered_lines(reader).try_fold(0, |total: Score, (n, l)| {
+        let l = l.context(ReadSnafu)?;
+        let hand = parse_hand(&rules, &l, &l)
+            .on_line(n)
+            .context(HandInvalidSnafu)?;
+        let score = score(&rules, hand).context(ScoreSnafu { round: n })?;
+        total.checked_add(score).context(OverflowSnafu)
+    })
+}
+
+fn parse_hand(rules: &Rules, s: &str, l: &str) -> HandResult<Round> {
+    use hand_error::*;
+
+    let mut parts = l.splitn(2, ' ').fuse();
+
+    let them = parts
+        .next()
+        .context(ThemMissingSnafu)
+        .locate(s, end_of(l))?;
+    let response = parts
+        .next()
+        .context(ResponseMissingSnafu)
+        .locate(s, end_of(l))?;
+
+    let them = rules
+        .them(them)
+        .context(ThemInvalidSnafu { code: them })
+        .locate(s, them)?;
+    let response = rules
+        .response(response)
+        .context(ResponseInvalidSnafu { code: response })
+        .locate(s, response)?;
+
+    Ok(Round { them, response })
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum HandError {
+    ThemMissing,
+
+    ResponseMissing,
+
+    #[snafu(display("{code:?} is not one of the opponent's shapes"))]
+    ThemInvalid {
+        code: String,
+    },
+
+    #[snafu(display("{code:?} is not one of our responses"))]
+    ResponseInvalid {
+        code: String,
+    },
+}
+
+type HandResult<T, E = Located<HandError>> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Read {
+        source: std::io::Error,
+    },
+
+    HandInvalid {
+        source: Located<HandError>,
+    },
+
+    #[snafu(display("Round {round} cannot be scored"))]
+    Score {
+        source: ScoreError,
+        round: usize,
+    },
+
+    #[snafu(display("The total score is too large"))]
+    Overflow,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Rounds of mostly known codes; `D` and `W` are in neither column.
+    const ARBITRARY_INPUT: &str = r"([ABCD] [XYZW]\n){0,10}";
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(15, total_score(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(12, total_score_part2(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn every_standard_round() -> Result<()> {
+        // From the shape we play (rock 1, paper 2, scissors 3) plus the
+        // outcome (lose 0, draw 3, win 6)
+        let rounds = [
+            ("A X", 4, 3),
+            ("A Y", 8, 4),
+            ("A Z", 3, 8),
+            ("B X", 1, 1),
+            ("B Y", 5, 5),
+            ("B Z", 9, 9),
+            ("C X", 7, 2),
+            ("C Y", 2, 6),
+            ("C Z", 6, 7),
+        ];
+
+        for (round, part1, part2) in rounds {
+            assert_eq!(part1, total_score(round)?, "{round}");
+            assert_eq!(part2, total_score_part2(round)?, "{round}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn lizard_spock() -> Result<()> {
+        let rules = ROCK_PAPER_SCISSORS_LIZARD_SPOCK.parse().unwrap();
+        let guide = parse_with("A Z\nD Y\nE X\n", rules)?;
+
+        assert_eq!(11 + 7 + 3, Day02::part1(&guide, ())?);
+        assert_eq!(11 + 7 + 3, Day02::part2(&guide, ())?);
+
+        let guide = parse_with("A V\n", guide.rules)?;
+        assert!(matches!(
+            Day02::part2(&guide, ()),
+            Err(Error::Score { round: 1, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn total_too_large() {
+        let rules = ROCK_PAPER_SCISSORS
+            .replace("A X 1", "A X 10000000000000000000")
+            .parse()
+            .unwrap();
+        let guide = parse_with("B X\nB X\n", rules).unwrap();
+
+        assert!(matches!(Day02::part1(&guide, ()), Err(Error::Overflow)));
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day02>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day02::parse(&s) {
+                let _ = Day02::part1(&parsed, Day02::PART1_PARAMS);
+                let _ = Day02::part2(&parsed, Day02::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_matches_str(s in ARBITRARY_INPUT) {
+            // Down to the round that cannot be scored
+            let from_str = (total_score(&s), total_score_part2(&s));
+            let from_reader = (
+                total_score_from_reader(s.as_bytes()),
+                total_score_part2_from_reader(s.as_bytes()),
+            );
+            prop_assert_eq!(format!("{from_str:?}"), format!("{from_reader:?}"));
+        }
+
+        #[test]
+        fn lizard_spock_never_panics(s in r"([A-F] [U-Z]\n){0,10}") {
+            let rules = ROCK_PAPER_SCISSORS_LIZARD_SPOCK.parse().unwrap();
+            if let Ok(guide) = parse_with(&s, rules) {
+                let _ = Day02::part1(&guide, Day02::PART1_PARAMS);
+                let _ = Day02::part2(&guide, Day02::PART2_PARAMS);
+            }
+        }
+    }
+}
@@ -1,227 +1,80 @@
+use aoc_common::{DayArgs, Solution};
+use clap::Parser;
+use day_02::{parse_with, total_score, total_score_part2, Day02, Rules};
 use snafu::prelude::*;
-use std::str::FromStr;
+use std::{fs, path::PathBuf};
 
-const INPUT: &str = include_str!("../input");
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
 
-type Score = u32;
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
 
-#[snafu::report]
-fn main() -> Result<()> {
-    let part1 = total_score(INPUT)?;
-    println!("{part1}");
-    assert_eq!(9241, part1);
-
-    let part2 = total_score_part2(INPUT)?;
-    println!("{part2}");
-    assert_eq!(14610, part2);
-
-    Ok(())
-}
-
-fn total_score(s: &str) -> Result<Score> {
-    let hands = s.lines().map(|l| {
-        let mut l = l.splitn(2, ' ').fuse();
-        let them = l.next().context(ThemMissingSnafu)?.parse::<Them>()?;
-        let us = l.next().context(UsMissingSnafu)?.parse::<Us>()?;
-        <Result<_>>::Ok((them, us))
-    });
-
-    itertools::process_results(hands, |hands| {
-        hands
-            .map(|(them, us)| play(them, us) + us.score())
-            .sum::<Score>()
-    })
-}
-
-fn total_score_part2(s: &str) -> Result<Score> {
-    let hands = s.lines().map(|l| {
-        let mut l = l.splitn(2, ' ').fuse();
-        let them = l.next().context(ThemMissingSnafu)?.parse::<Them>()?;
-        let outcome = l.next().context(OutcomeMissingSnafu)?.parse::<Outcome>()?;
-        <Result<_>>::Ok((them, outcome))
-    });
-
-    itertools::process_results(hands, |hands| {
-        hands
-            .map(|(them, outcome)| {
-                let us = outcome - them;
-                play(them, us) + us.score()
-            })
-            .sum::<Score>()
-    })
-}
-
-const WIN: Score = 6;
-const DRAW: Score = 3;
-const LOSE: Score = 0;
-
-fn play(them: Them, us: Us) -> Score {
-    use {Them as T, Us as U};
-    match (them, us) {
-        (T::Rock, U::Paper) | (T::Paper, U::Scissors) | (T::Scissors, U::Rock) => WIN,
-
-        (T::Rock, U::Rock) | (T::Paper, U::Paper) | (T::Scissors, U::Scissors) => DRAW,
-
-        (T::Rock, U::Scissors) | (T::Paper, U::Rock) | (T::Scissors, U::Paper) => LOSE,
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Them {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-#[derive(Debug, Snafu)]
-struct UnknownThemError {
-    s: String,
-}
-
-impl FromStr for Them {
-    type Err = UnknownThemError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Them::*;
-
-        Ok(match s {
-            "A" => Rock,
-            "B" => Paper,
-            "C" => Scissors,
-            _ => return UnknownThemSnafu { s }.fail(),
-        })
-    }
+    /// Score the guide with the rules in this file, such as a game
+    /// with more shapes, instead of solving the puzzle
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Us {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl Us {
-    fn score(&self) -> Score {
-        use Us::*;
-
-        match self {
-            Rock => 1,
-            Paper => 2,
-            Scissors => 3,
-        }
-    }
-}
-
-#[derive(Debug, Snafu)]
-struct UnknownUsError {
-    s: String,
+#[snafu::report]
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let result = run(&args);
+    args.day
+        .report_failure(Day02::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
 }
 
-impl FromStr for Us {
-    type Err = UnknownUsError;
+fn run(args: &Args) -> Result<()> {
+    let Args { day: args, rules } = args;
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Us::*;
+    if let Some(path) = rules {
+        let rules = fs::read_to_string(path).context(RulesReadSnafu { path })?;
+        let rules = rules.parse::<Rules>().context(RulesSnafu { path })?;
+        let guide = parse_with(&input, rules)?;
 
-        Ok(match s {
-            "X" => Rock,
-            "Y" => Paper,
-            "Z" => Scissors,
-            _ => return UnknownUsSnafu { s }.fail(),
-        })
+        println!("Part 1: {}", Day02::part1(&guide, ())?);
+        println!("Part 2: {}", Day02::part2(&guide, ())?);
+        return Ok(());
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-enum Outcome {
-    Lose,
-    Draw,
-    Win,
-}
-
-#[derive(Debug, Snafu)]
-struct UnknownOutcomeError {
-    s: String,
-}
+    let mut output = args
+        .output(Day02::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
 
-impl FromStr for Outcome {
-    type Err = UnknownOutcomeError;
+    output.solve(1, || total_score(&input))?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Outcome::*;
-
-        Ok(match s {
-            "X" => Lose,
-            "Y" => Draw,
-            "Z" => Win,
-            _ => return UnknownOutcomeSnafu { s }.fail(),
-        })
-    }
-}
+    output.solve(2, || total_score_part2(&input))?;
 
-impl ::core::ops::Sub<Them> for Outcome {
-    type Output = Us;
+    output.finish().context(AnswersSnafu)?;
 
-    fn sub(self, rhs: Them) -> Self::Output {
-        match (rhs, self) {
-            (Them::Rock, Outcome::Lose) => Us::Scissors,
-            (Them::Rock, Outcome::Draw) => Us::Rock,
-            (Them::Rock, Outcome::Win) => Us::Paper,
-
-            (Them::Paper, Outcome::Lose) => Us::Rock,
-            (Them::Paper, Outcome::Draw) => Us::Paper,
-            (Them::Paper, Outcome::Win) => Us::Scissors,
-
-            (Them::Scissors, Outcome::Lose) => Us::Paper,
-            (Them::Scissors, Outcome::Draw) => Us::Scissors,
-            (Them::Scissors, Outcome::Win) => Us::Rock,
-        }
-    }
+    Ok(())
 }
 
 #[derive(Debug, Snafu)]
 enum Error {
-    ThemMissing,
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-    UsMissing,
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    OutcomeMissing,
-
-    #[snafu(context(false))]
-    BadThem {
-        source: UnknownThemError,
+    #[snafu(display("Could not read the rules from {}", path.display()))]
+    RulesRead {
+        source: std::io::Error,
+        path: PathBuf,
     },
 
-    #[snafu(context(false))]
-    BadUs {
-        source: UnknownUsError,
+    #[snafu(display("The rules in {} are invalid", path.display()))]
+    Rules {
+        source: day_02::RulesError,
+        path: PathBuf,
     },
 
-    #[snafu(context(false))]
-    BadOutcome {
-        source: UnknownOutcomeError,
-    },
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_02::Error },
 }
 
 type Result<T, E = Error> = std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = include_str!("../input.test");
-
-    #[test]
-    #[snafu::report]
-    fn example() -> Result<()> {
-        assert_eq!(15, total_score(INPUT)?);
-        Ok(())
-    }
-
-    #[test]
-    #[snafu::report]
-    fn example_part2() -> Result<()> {
-        assert_eq!(12, total_score_part2(INPUT)?);
-        Ok(())
-    }
-}
@@ -0,0 +1,552 @@
+use crate::Score;
+use aoc_common::{LocateExt, Located};
+use snafu::prelude::*;
+use std::{fmt, str::FromStr};
+
+/// The puzzle's game.
+pub const ROCK_PAPER_SCISSORS: &str = "\
+shape Rock     A X 1
+shape Paper    B Y 2
+shape Scissors C Z 3
+
+Rock beats Scissors
+Paper beats Rock
+Scissors beats Paper
+
+X means lose
+Y means draw
+Z means win
+";
+
+/// Rock, paper, scissors, lizard, Spock. Only `X`, `Y` and `Z` have a
+/// meaning when the response is the outcome to aim for.
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = "\
+shape Rock     A V 1
+shape Paper    B W 2
+shape Scissors C X 3
+shape Lizard   D Y 4
+shape Spock    E Z 5
+
+Rock beats Scissors
+Rock beats Lizard
+Paper beats Rock
+Paper beats Spock
+Scissors beats Paper
+Scissors beats Lizard
+Lizard beats Paper
+Lizard beats Spock
+Spock beats Rock
+Spock beats Scissors
+
+X means lose
+Y means draw
+Z means win
+";
+
+pub const WIN: Score = 6;
+pub const DRAW: Score = 3;
+pub const LOSE: Score = 0;
+
+/// A game in the style of rock, paper, scissors, and how to read a
+/// strategy guide for it.
+///
+/// Rules are written one per line, with `#` starting a comment:
+///
+/// - `shape NAME THEM US SCORE` adds a shape, with the codes each
+///   column of the guide uses for it and what playing it scores.
+/// - `NAME beats NAME` says who wins when those shapes meet.
+/// - `CODE means lose|draw|win` gives a response its meaning when it
+///   is the outcome to aim for.
+///
+/// Every pair of shapes must have a winner, and every shape must beat
+/// as many shapes as beat it, so each outcome can always be reached.
+/// That needs an odd number of shapes, and there must be at least 3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    /// `beats[a][b]` when shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+    /// Every code that may appear in our column.
+    responses: Vec<ResponseRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShapeRule {
+    name: String,
+    them: String,
+    score: Score,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResponseRule {
+    code: String,
+    shape: Option<Shape>,
+    outcome: Option<Outcome>,
+}
+
+/// One of the shapes of a set of [`Rules`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Shape(usize);
+
+/// One of the codes in our column of the guide, which each part
+/// interprets differently.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Response(usize);
+
+/// One line of the strategy guide.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub them: Shape,
+    pub response: Response,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub fn score(self) -> Score {
+        match self {
+            Outcome::Lose => LOSE,
+            Outcome::Draw => DRAW,
+            Outcome::Win => WIN,
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        ROCK_PAPER_SCISSORS
+            .parse()
+            .expect("The standard rules are valid")
+    }
+}
+
+impl Rules {
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    pub fn responses(&self) -> impl Iterator<Item = Response> {
+        (0..self.responses.len()).map(Response)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    /// The code for `shape` in the opponent's column.
+    pub fn them_code(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].them
+    }
+
+    pub fn response_code(&self, response: Response) -> &str {
+        &self.responses[response.0].code
+    }
+
+    pub fn them(&self, code: &str) -> Option<Shape> {
+        self.shapes.iter().position(|s| s.them == code).map(Shape)
+    }
+
+    pub fn response(&self, code: &str) -> Option<Response> {
+        self.responses
+            .iter()
+            .position(|r| r.code == code)
+            .map(Response)
+    }
+
+    /// How the game goes for us.
+    pub fn play(&self, them: Shape, us: Shape) -> Outcome {
+        if self.beats[us.0][them.0] {
+            Outcome::Win
+        } else if self.beats[them.0][us.0] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Our score when the response is the shape to play.
+    pub fn score_as_shape(&self, round: Round) -> Result<Score, ScoreError> {
+        let response = &self.responses[round.response.0];
+        let us = response.shape.context(score_error::NotAShapeSnafu {
+            code: &response.code,
+        })?;
+
+        Ok(self.score(round.them, us))
+    }
+
+    /// Our score when the response is the outcome to aim for. When
+    /// several shapes reach that outcome, we play the one that scores
+    /// the most.
+    pub fn score_as_outcome(&self, round: Round) -> Result<Score, ScoreError> {
+        let response = &self.responses[round.response.0];
+        let outcome = response.outcome.context(score_error::NotAnOutcomeSnafu {
+            code: &response.code,
+        })?;
+
+        let us = self
+            .shapes()
+            .filter(|&us| self.play(round.them, us) == outcome)
+            .max_by_key(|us| self.shapes[us.0].score)
+            .context(score_error::UnreachableSnafu {
+                outcome,
+                them: self.name(round.them),
+            })?;
+
+        Ok(self.score(round.them, us))
+    }
+
+    fn score(&self, them: Shape, us: Shape) -> Score {
+        self.play(them, us).score() + self.shapes[us.0].score
+    }
+
+    fn response_mut(&mut self, code: &str) -> &mut ResponseRule {
+        let i = match self.responses.iter().position(|r| r.code == code) {
+            Some(i) => i,
+            None => {
+                self.responses.push(ResponseRule {
+                    code: code.to_owned(),
+                    shape: None,
+                    outcome: None,
+                });
+                self.responses.len() - 1
+            }
+        };
+        &mut self.responses[i]
+    }
+
+    fn shape_named(&self, name: &str) -> Option<Shape> {
+        self.shapes.iter().position(|s| s.name == name).map(Shape)
+    }
+
+    fn parse_line(&mut self, s: &str, l: &str) -> Result<(), Located<RuleError>> {
+        use rule_error::*;
+
+        let content = l.split_once('#').map_or(l, |(c, _)| c);
+        let words = content.split_whitespace().collect::<Vec<_>>();
+
+        match words[..] {
+            [] => {}
+
+            ["shape", name, them, us, score] => {
+                let score = score.parse().context(ScoreInvalidSnafu).locate(s, score)?;
+                let shape = Shape(self.shapes.len());
+
+                if self.shape_named(name).is_some() {
+                    return NameRepeatedSnafu { name }.fail().locate(s, name);
+                }
+                if self.them(them).is_some() {
+                    return CodeRepeatedSnafu { code: them }.fail().locate(s, them);
+                }
+                let response = self.response_mut(us);
+                if response.shape.is_some() {
+                    return CodeRepeatedSnafu { code: us }.fail().locate(s, us);
+                }
+                response.shape = Some(shape);
+
+                self.shapes.push(ShapeRule {
+                    name: name.to_owned(),
+                    them: them.to_owned(),
+                    score,
+                });
+                for row in &mut self.beats {
+                    row.push(false);
+                }
+                self.beats.push(vec![false; self.shapes.len()]);
+            }
+
+            [winner, "beats", loser] => {
+                let shape = |name| {
+                    self.shape_named(name)
+                        .context(ShapeUnknownSnafu { name })
+                        .locate(s, name)
+                };
+                let (w, l) = (shape(winner)?, shape(loser)?);
+
+                if w == l {
+                    return BeatsItselfSnafu { name: winner }.fail().locate(s, loser);
+                }
+                if self.beats[l.0][w.0] {
+                    return ContradictionSnafu { winner, loser }
+                        .fail()
+                        .locate(s, winner);
+                }
+                self.beats[w.0][l.0] = true;
+            }
+
+            [code, "means", outcome] => {
+                let outcome = match outcome {
+                    "lose" => Outcome::Lose,
+                    "draw" => Outcome::Draw,
+                    "win" => Outcome::Win,
+                    _ => return OutcomeUnknownSnafu { outcome }.fail().locate(s, outcome),
+                };
+                let response = self.response_mut(code);
+                if response.outcome.is_some() {
+                    return CodeRepeatedSnafu { code }.fail().locate(s, code);
+                }
+                response.outcome = Some(outcome);
+            }
+
+            _ => return RuleUnknownSnafu.fail().locate(s, content.trim()),
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), RulesError> {
+        use rules_error::*;
+
+        let count = self.shapes.len();
+        ensure!(count >= 3 && count % 2 == 1, ShapeCountSnafu { count });
+
+        for a in self.shapes() {
+            for b in self.shapes().filter(|&b| b > a) {
+                ensure!(
+                    self.beats[a.0][b.0] || self.beats[b.0][a.0],
+                    UndecidedSnafu {
+                        a: self.name(a),
+                        b: self.name(b),
+                    }
+                );
+            }
+
+            let wins = self.beats[a.0].iter().filter(|&&b| b).count();
+            let losses = self.beats.iter().filter(|row| row[a.0]).count();
+            ensure!(
+                wins == losses,
+                UnbalancedSnafu {
+                    name: self.name(a),
+                    wins,
+                    losses,
+                }
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Rules {
+    type Err = RulesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Rules {
+            shapes: Vec::new(),
+            beats: Vec::new(),
+            responses: Vec::new(),
+        };
+
+        for l in s.lines() {
+            rules
+                .parse_line(s, l)
+                .context(rules_error::RuleInvalidSnafu)?;
+        }
+
+        rules.validate()?;
+        Ok(rules)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum RuleError {
+    #[snafu(display("The rule is not one of `shape`, `beats` or `means`"))]
+    RuleUnknown,
+
+    #[snafu(display("The score is not a number"))]
+    ScoreInvalid { source: std::num::ParseIntError },
+
+    #[snafu(display("There is already a shape named {name}"))]
+    NameRepeated { name: String },
+
+    #[snafu(display("The code {code} is already in use"))]
+    CodeRepeated { code: String },
+
+    #[snafu(display("There is no shape named {name}"))]
+    ShapeUnknown { name: String },
+
+    #[snafu(display("{name} cannot beat itself"))]
+    BeatsItself { name: String },
+
+    #[snafu(display("{loser} already beats {winner}"))]
+    Contradiction { winner: String, loser: String },
+
+    #[snafu(display("{outcome:?} is not one of lose, draw or win"))]
+    OutcomeUnknown { outcome: String },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum RulesError {
+    #[snafu(display("The rules are invalid"))]
+    RuleInvalid { source: Located<RuleError> },
+
+    #[snafu(display("There must be an odd number of shapes, at least 3, not {count}"))]
+    ShapeCount { count: usize },
+
+    #[snafu(display("Nothing says whether {a} or {b} wins"))]
+    Undecided { a: String, b: String },
+
+    #[snafu(display("{name} beats {wins} shapes but loses to {losses}"))]
+    Unbalanced {
+        name: String,
+        wins: usize,
+        losses: usize,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ScoreError {
+    #[snafu(display("The response {code} is not a shape"))]
+    NotAShape { code: String },
+
+    #[snafu(display("The response {code} is not an outcome"))]
+    NotAnOutcome { code: String },
+
+    #[snafu(display("No shape can {outcome} against {them}"))]
+    Unreachable { outcome: Outcome, them: String },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round(rules: &Rules, them: &str, response: &str) -> Round {
+        Round {
+            them: rules.them(them).unwrap(),
+            response: rules.response(response).unwrap(),
+        }
+    }
+
+    #[test]
+    #[snafu::report]
+    fn lizard_spock() -> Result<(), RulesError> {
+        let rules = ROCK_PAPER_SCISSORS_LIZARD_SPOCK.parse::<Rules>()?;
+
+        // Spock vaporizes rock; lizard eats paper
+        assert_eq!(
+            rules.score_as_shape(round(&rules, "A", "Z")).unwrap(),
+            6 + 5
+        );
+        assert_eq!(
+            rules.score_as_shape(round(&rules, "B", "Y")).unwrap(),
+            6 + 4
+        );
+        assert_eq!(
+            rules.score_as_shape(round(&rules, "D", "Y")).unwrap(),
+            3 + 4
+        );
+        assert_eq!(
+            rules.score_as_shape(round(&rules, "E", "W")).unwrap(),
+            6 + 2
+        );
+
+        // Paper and Spock both beat rock; Spock scores more
+        assert_eq!(
+            rules.score_as_outcome(round(&rules, "A", "Z")).unwrap(),
+            6 + 5
+        );
+        // Lizard and scissors both lose to rock
+        assert_eq!(rules.score_as_outcome(round(&rules, "A", "X")).unwrap(), 4);
+        assert!(matches!(
+            rules.score_as_outcome(round(&rules, "A", "V")),
+            Err(ScoreError::NotAnOutcome { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn every_outcome_is_reachable() {
+        for rules in [ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_LIZARD_SPOCK] {
+            let rules = rules.parse::<Rules>().unwrap();
+
+            for them in rules.shapes() {
+                let outcomes = rules
+                    .shapes()
+                    .map(|us| rules.play(them, us))
+                    .collect::<Vec<_>>();
+                let wins = outcomes.iter().filter(|&&o| o == Outcome::Win).count();
+                let losses = outcomes.iter().filter(|&&o| o == Outcome::Lose).count();
+                assert_eq!(wins, (rules.shapes().count() - 1) / 2);
+                assert_eq!(wins, losses);
+            }
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for rules in [ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_LIZARD_SPOCK] {
+            let rules = rules.parse::<Rules>().unwrap();
+
+            for shape in rules.shapes() {
+                assert_eq!(Some(shape), rules.them(rules.them_code(shape)));
+            }
+            for response in rules.responses() {
+                assert_eq!(
+                    Some(response),
+                    rules.response(rules.response_code(response))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let error = |rules: &str| rules.parse::<Rules>().unwrap_err().to_string();
+
+        let shape_count = "There must be an odd number of shapes, at least 3";
+        assert_eq!(error(""), format!("{shape_count}, not 0"));
+        assert_eq!(
+            error("shape Rock A X 1\nX means win\n"),
+            format!("{shape_count}, not 1")
+        );
+        assert_eq!(
+            error("shape Rock A X 1\nshape Paper B Y 2\nshape Scissors C Z 3\n"),
+            "Nothing says whether Rock or Paper wins",
+        );
+        assert_eq!(
+            error(
+                "shape Rock A X 1\nshape Paper B Y 2\nshape Scissors C Z 3\n\
+                 Paper beats Rock\nPaper beats Scissors\nRock beats Scissors\n"
+            ),
+            "Paper beats 2 shapes but loses to 0",
+        );
+
+        let located = |rules: &str| match rules.parse::<Rules>() {
+            Err(RulesError::RuleInvalid { source }) => source.source,
+            other => panic!("Expected a rule to be invalid, got {other:?}"),
+        };
+        assert!(matches!(
+            located("shape Rock A X 1\nRock beats Rock\n"),
+            RuleError::BeatsItself { .. }
+        ));
+        assert!(matches!(
+            located("shape Rock A X 1\nshape Paper A Y 2\n"),
+            RuleError::CodeRepeated { .. }
+        ));
+        assert!(matches!(
+            located("shape Rock A X 1\nshape Paper B Y 2\nPaper beats Rock\nRock beats Paper\n"),
+            RuleError::Contradiction { .. }
+        ));
+        assert!(matches!(
+            located("Rock smashes Scissors\n"),
+            RuleError::RuleUnknown
+        ));
+    }
+}
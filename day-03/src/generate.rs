@@ -0,0 +1,50 @@
+use crate::Day03;
+use aoc_common::generate::{Generate, GenerateRng, Rng, SliceRandom};
+
+/// `size` is the number of groups of three elves.
+impl Generate for Day03 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for _ in 0..size {
+            let mut items: Vec<_> = ('a'..='z').chain('A'..='Z').collect();
+            items.shuffle(rng);
+
+            // Each elf gets their own items, so the badge is the only
+            // item all three have in common.
+            let (badge, items) = items.split_first().expect("Not empty");
+
+            for items in items.chunks_exact(items.len() / 3) {
+                // Likewise, each compartment gets its own items, so
+                // the duplicate is the only item found in both.
+                let (duplicate, items) = items.split_first().expect("Not empty");
+                let (front_only, back_only) = items.split_at(items.len() / 2);
+
+                // Room for both the duplicate and the badge
+                let len = rng.gen_range(2..=12);
+                let badge_in_front = rng.gen();
+
+                let mut compartment = |only: &[char], has_badge: bool| {
+                    let mut compartment = vec![*duplicate];
+                    if has_badge {
+                        compartment.push(*badge);
+                    }
+                    while compartment.len() < len {
+                        compartment.push(*only.choose(rng).expect("Not empty"));
+                    }
+                    compartment.shuffle(rng);
+                    compartment
+                };
+
+                let front = compartment(front_only, badge_in_front);
+                let back = compartment(back_only, !badge_in_front);
+
+                s.extend(front);
+                s.extend(back);
+                s.push('\n');
+            }
+        }
+
+        s
+    }
+}
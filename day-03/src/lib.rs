@@ -0,0 +1,237 @@
+//! Day 3: Rucksack Reorganization
+//!
+//! Each line is a rucksack whose two halves are its compartments;
+//! items are letters, prioritised `a`–`z` then `A`–`Z`. Part 1 sums
+//! the priority of the item found in both compartments of each
+//! rucksack; part 2 sums the badge shared by each group of three.
+
+use aoc_common::{input::numbered_lines, LocateExt, Located, Solution, WithinExt};
+use snafu::prelude::*;
+use std::{collections::BTreeSet, io::BufRead};
+
+mod generate;
+
+type Priority = u32;
+
+/// The parsed input is each rucksack's line of items.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    /// The priority of each item in each knapsack.
+    type Input<'a> = Vec<Vec<u8>>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = Priority;
+
+    type Part2Params = ();
+    type Part2 = Priority;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        s.lines()
+            .map(|l| parse_knapsack(s, l).context(InvalidKnapsackContentSnafu))
+            .collect()
+    }
+
+    fn part1(knapsacks: &Self::Input<'_>, (): ()) -> Result<Priority> {
+        knapsacks
+            .iter()
+            .zip(1_usize..)
+            .map(|(l, line)| duplicated_priority(l, line))
+            .sum()
+    }
+
+    fn part2(knapsacks: &Self::Input<'_>, (): ()) -> Result<Priority> {
+        knapsacks
+            .chunks_exact(3)
+            .map(|group| badge_priority([&group[0], &group[1], &group[2]]))
+            .sum()
+    }
+}
+
+/// The summed priorities of the item in both compartments of each rucksack.
+pub fn sum_of_duplicated_priorities(s: &str) -> Result<Priority> {
+    Day03::part1(&Day03::parse(s)?, ())
+}
+
+/// The summed priorities of each group of three elves' shared badge.
+pub fn sum_of_group_badge_priorities(s: &str) -> Result<Priority> {
+    Day03::part2(&Day03::parse(s)?, ())
+}
+
+/// As [`sum_of_duplicated_priorities`], reading one line at a time.
+pub fn sum_of_duplicated_priorities_from_reader(reader: impl BufRead) -> Result<Priority> {
+    knapsacks_from_reader(reader)
+        .zip(1_usize..)
+        .map(|(l, line)| duplicated_priority(&l?, line))
+        .sum()
+}
+
+/// As [`sum_of_group_badge_priorities`], reading one group of lines at
+/// a time.
+pub fn sum_of_group_badge_priorities_from_reader(reader: impl BufRead) -> Result<Priority> {
+    let mut knapsacks = knapsacks_from_reader(reader);
+    let mut total = 0;
+
+    loop {
+        let group = [knapsacks.next(), knapsacks.next(), knapsacks.next()];
+        let [Some(a), Some(b), Some(c)] = group else {
+            // As with `chunks_exact`, an incomplete group is ignored...
+            for l in group.into_iter().flatten() {
+                // ...once we know that it was valid
+                l?;
+            }
+            return Ok(total);
+        };
+        total += badge_priority([&a?, &b?, &c?])?;
+    }
+}
+
+fn knapsacks_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Vec<u8>>> {
+    numbered_lines(reader).map(|(n, l)| {
+        let l = l.context(ReadSnafu)?;
+        parse_knapsack(&l, &l)
+            .on_line(n)
+            .context(InvalidKnapsackContentSnafu)
+    })
+}
+
+/// The priority of each item.
+fn parse_knapsack(s: &str, l: &str) -> Result<Vec<u8>, Located<InvalidContentError>> {
+    let l = l.trim();
+    l.char_indices()
+        .map(|(i, v)| item_priority(v).locate(s, &l[i..][..v.len_utf8()]))
+        .collect()
+}
+
+fn duplicated_priority(knapsack: &[u8], line: usize) -> Result<Priority> {
+    ensure!(
+        knapsack.len().is_multiple_of(2),
+        NonEvenLengthSnafu { line }
+    );
+    let half_len = knapsack.len() / 2;
+    let (front, back) = knapsack.split_at(half_len);
+    let [front, back] = [front, back].map(Contents::from);
+    let common = Contents::intersect(&front, &back)?;
+    Ok(Priority::from(common))
+}
+
+fn badge_priority(group: [&[u8]; 3]) -> Result<Priority> {
+    let [a, b, c] = group.map(Contents::from);
+    let common = Contents::intersect_3(&a, &b, &c)?;
+    Ok(Priority::from(common))
+}
+
+struct Contents(BTreeSet<u8>);
+
+impl Contents {
+    fn intersect(&self, other: &Self) -> Result<u8> {
+        self.0
+            .intersection(&other.0)
+            .next()
+            .copied()
+            .context(NoIntersectionSnafu)
+    }
+
+    fn intersect_3(&self, b: &Self, c: &Self) -> Result<u8> {
+        let intermediate = Self(&self.0 & &b.0);
+        Contents::intersect(&intermediate, c)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct InvalidContentError {
+    v: char,
+}
+
+impl From<&[u8]> for Contents {
+    fn from(value: &[u8]) -> Self {
+        Self(value.iter().copied().collect())
+    }
+}
+
+fn item_priority(v: char) -> Result<u8, InvalidContentError> {
+    Ok(match v {
+        'a'..='z' => v as u8 - b'a' + 1,
+        'A'..='Z' => v as u8 - b'A' + 1 + 26,
+        _ => return InvalidContentSnafu { v }.fail(),
+    })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Read {
+        source: std::io::Error,
+    },
+
+    NonEvenLength {
+        line: usize,
+    },
+
+    InvalidKnapsackContent {
+        source: Located<InvalidContentError>,
+    },
+
+    NoIntersection,
+}
+
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Rucksacks of any length, including odd ones that cannot be split
+    /// in two, and groups that may be cut short.
+    const ARBITRARY_INPUT: &str = r"([a-zA-Z0-9]{0,12}\n){0,9}";
+
+    #[test]
+    fn exercise() -> Result<()> {
+        assert_eq!(157, sum_of_duplicated_priorities(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    fn exercise_part2() -> Result<()> {
+        assert_eq!(70, sum_of_group_badge_priorities(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day03>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day03::parse(&s) {
+                let _ = Day03::part1(&parsed, Day03::PART1_PARAMS);
+                let _ = Day03::part2(&parsed, Day03::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_agrees_with_str(s in ARBITRARY_INPUT) {
+            // The reader finds an odd rucksack as soon as it reads it,
+            // but parsing everything up front first finds any unknown
+            // item later on
+            let from_str = (sum_of_duplicated_priorities(&s), sum_of_group_badge_priorities(&s));
+            let from_reader = (
+                sum_of_duplicated_priorities_from_reader(s.as_bytes()),
+                sum_of_group_badge_priorities_from_reader(s.as_bytes()),
+            );
+            prop_assert_eq!(from_str.0.ok(), from_reader.0.ok());
+            prop_assert_eq!(from_str.1.ok(), from_reader.1.ok());
+        }
+    }
+}
@@ -1,126 +1,42 @@
-#![feature(iter_array_chunks)]
-
+use aoc_common::{DayArgs, Solution};
+use day_03::{sum_of_duplicated_priorities, sum_of_group_badge_priorities, Day03};
 use snafu::prelude::*;
-use std::collections::BTreeSet;
-
-const INPUT: &str = include_str!("../input");
 
-type Priority = u32;
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
 
 #[snafu::report]
 fn main() -> Result<()> {
-    let part1 = sum_of_duplicated_priorities(INPUT)?;
-    println!("{part1}");
-    assert_eq!(7878, part1);
-
-    let part2 = sum_of_group_badge_priorities(INPUT)?;
-    println!("{part2}");
-    assert_eq!(2760, part2);
-
-    Ok(())
+    let args = DayArgs::from_env();
+    let result = run(&args);
+    args.report_failure(Day03::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
 }
 
-fn sum_of_duplicated_priorities(s: &str) -> Result<Priority> {
-    s.lines()
-        .map(|l| {
-            let l = l.trim();
-            // String contains only ASCII
-            let l = l.as_bytes();
+fn run(args: &DayArgs) -> Result<()> {
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
+    let mut output = args
+        .output(Day03::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
 
-            ensure!(l.len() % 2 == 0, NonEvenLengthSnafu);
-            let half_len = l.len() / 2;
-            let (front, back) = l.split_at(half_len);
-            let [front, back] =
-                [front, back].map(|l| Contents::try_from(l).context(InvalidKnapsackContentSnafu));
-            let common = Contents::intersect(&front?, &back?)?;
-            Ok(Priority::from(common))
-        })
-        .sum()
-}
+    output.solve(1, || sum_of_duplicated_priorities(&input))?;
 
-fn sum_of_group_badge_priorities(s: &str) -> Result<Priority> {
-    s.lines()
-        .map(|l| {
-            let l = l.trim();
-            // String contains only ASCII
-            let l = l.as_bytes();
+    output.solve(2, || sum_of_group_badge_priorities(&input))?;
 
-            Contents::try_from(l).context(InvalidKnapsackContentSnafu)
-        })
-        .array_chunks()
-        .map(|[a, b, c]| {
-            let common = Contents::intersect_3(&a?, &b?, &c?)?;
-            Ok(Priority::from(common))
-        })
-        .sum()
-}
-
-struct Contents(BTreeSet<u8>);
-
-impl Contents {
-    fn intersect(&self, other: &Self) -> Result<u8> {
-        self.0
-            .intersection(&other.0)
-            .next()
-            .copied()
-            .context(NoIntersectionSnafu)
-    }
-
-    fn intersect_3(&self, b: &Self, c: &Self) -> Result<u8> {
-        let intermediate = Self(&self.0 & &b.0);
-        Contents::intersect(&intermediate, c)
-    }
-}
+    output.finish().context(AnswersSnafu)?;
 
-#[derive(Debug, Snafu)]
-struct InvalidContentError {
-    v: char,
-}
-
-impl TryFrom<&[u8]> for Contents {
-    type Error = InvalidContentError;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        value
-            .iter()
-            .map(|&v| {
-                Ok(match v {
-                    b'a'..=b'z' => v - b'a' + 1,
-                    b'A'..=b'Z' => v - b'A' + 1 + 26,
-                    _ => return InvalidContentSnafu { v }.fail(),
-                })
-            })
-            .collect::<Result<_, _>>()
-            .map(Self)
-    }
+    Ok(())
 }
 
 #[derive(Debug, Snafu)]
 enum Error {
-    NonEvenLength,
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-    InvalidKnapsackContent { source: InvalidContentError },
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    NoIntersection,
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_03::Error },
 }
 
-type Result<T, E = Error> = ::std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = include_str!("../input.test");
-
-    #[test]
-    fn exercise() -> Result<()> {
-        assert_eq!(157, sum_of_duplicated_priorities(INPUT)?);
-        Ok(())
-    }
-
-    #[test]
-    fn exercise_part2() -> Result<()> {
-        assert_eq!(70, sum_of_group_badge_priorities(INPUT)?);
-        Ok(())
-    }
-}
+type Result<T, E = Error> = std::result::Result<T, E>;
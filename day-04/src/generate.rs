@@ -0,0 +1,25 @@
+use crate::Day04;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::fmt::Write;
+
+/// `size` is the number of pairs of elves.
+impl Generate for Day04 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for _ in 0..size {
+            let mut range = || {
+                let start = rng.gen_range(1..=99);
+                let end = rng.gen_range(start..=99);
+                (start, end)
+            };
+            let (a_start, a_end) = range();
+            let (b_start, b_end) = range();
+
+            writeln!(s, "{a_start}-{a_end},{b_start}-{b_end}")
+                .expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
@@ -0,0 +1,212 @@
+//! Day 4: Camp Cleanup
+//!
+//! Each line is a pair of elves and the inclusive range of sections
+//! each has been assigned. Part 1 counts the pairs where one range
+//! contains the other; part 2 counts the pairs that overlap at all.
+
+use aoc_common::{
+    diagnostic::end_of, input::numbered_lines, LocateExt, Located, Solution, WithinExt,
+};
+use snafu::prelude::*;
+use std::{io::BufRead, num::ParseIntError, ops::RangeInclusive};
+
+mod generate;
+
+type Id = u32;
+type Elf = RangeInclusive<Id>;
+
+/// The parsed input is each pair's two section ranges.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<[Elf; 2]>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_elf_pairs(s).collect()
+    }
+
+    fn part1(pairs: &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|[e1, e2]| either_fully_contains(e1, e2))
+            .count())
+    }
+
+    fn part2(pairs: &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|[e1, e2]| either_any_contains(e1, e2))
+            .count())
+    }
+}
+
+/// How many pairs have one range entirely within the other.
+pub fn count_of_fully_contained_pairs(s: &str) -> Result<usize> {
+    Day04::part1(&Day04::parse(s)?, ())
+}
+
+/// How many pairs have ranges that overlap at all.
+pub fn count_of_any_contained_pairs(s: &str) -> Result<usize> {
+    Day04::part2(&Day04::parse(s)?, ())
+}
+
+/// As [`count_of_fully_contained_pairs`], reading one line at a time.
+pub fn count_of_fully_contained_pairs_from_reader(reader: impl BufRead) -> Result<usize> {
+    elf_pairs_from_reader(reader).try_fold(0, |n, pair| {
+        let [e1, e2] = pair?;
+        Ok(n + usize::from(either_fully_contains(&e1, &e2)))
+    })
+}
+
+/// As [`count_of_any_contained_pairs`], reading one line at a time.
+pub fn count_of_any_contained_pairs_from_reader(reader: impl BufRead) -> Result<usize> {
+    elf_pairs_from_reader(reader).try_fold(0, |n, pair| {
+        let [e1, e2] = pair?;
+        Ok(n + usize::from(either_any_contains(&e1, &e2)))
+    })
+}
+
+fn elf_pairs_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<[Elf; 2]>> {
+    numbered_lines(reader).map(|(n, l)| {
+        let l = l.context(ReadSnafu)?;
+        parse_elf_pair(&l, &l).on_line(n).context(PairInvalidSnafu)
+    })
+}
+
+fn parse_elf_pairs(s: &str) -> impl Iterator<Item = Result<[Elf; 2]>> + '_ {
+    s.lines()
+        .map(|l| parse_elf_pair(s, l).context(PairInvalidSnafu))
+}
+
+fn parse_elf_pair<'a>(s: &str, l: &'a str) -> PairResult<[Elf; 2]> {
+    use pair_error::*;
+
+    let (e1, e2) = l
+        .split_once(',')
+        .context(MissingPairSnafu)
+        .locate(s, end_of(l))?;
+
+    let split = |e: &'a str| {
+        e.split_once('-')
+            .context(MissingRangePartSnafu)
+            .locate(s, end_of(e))
+    };
+    let (e1, e2) = (split(e1)?, split(e2)?);
+
+    let range = |(start, end): (&str, &str)| {
+        let parse = |id: &str| {
+            id.parse::<Id>()
+                .context(InvalidIdSnafu { id })
+                .locate(s, id)
+        };
+        Ok(parse(start)?..=parse(end)?)
+    };
+    Ok([range(e1)?, range(e2)?])
+}
+
+fn either_fully_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
+where
+    T: Ord,
+{
+    fully_contains(a, b) || fully_contains(b, a)
+}
+
+fn fully_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
+where
+    T: Ord,
+{
+    a.start() >= b.start() && a.end() <= b.end()
+}
+
+fn either_any_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
+where
+    T: Ord,
+{
+    a.contains(b.start()) || a.contains(b.end()) || b.contains(a.start()) || b.contains(a.end())
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Read { source: std::io::Error },
+
+    PairInvalid { source: Located<PairError> },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum PairError {
+    MissingPair,
+
+    MissingRangePart,
+
+    InvalidId { source: ParseIntError, id: String },
+}
+
+type PairResult<T, E = Located<PairError>> = std::result::Result<T, E>;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Pairs of ranges, some backwards, and some with a `,` where the
+    /// second range needs its `-`.
+    const ARBITRARY_INPUT: &str = r"([0-9]{1,3}-[0-9]{1,3},[0-9]{1,3}[-,][0-9]{1,3}\n){0,5}";
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(2, count_of_fully_contained_pairs(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(4, count_of_any_contained_pairs(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day04>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day04::parse(&s) {
+                let _ = Day04::part1(&parsed, Day04::PART1_PARAMS);
+                let _ = Day04::part2(&parsed, Day04::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_matches_str(s in ARBITRARY_INPUT) {
+            // Down to the column of a malformed range
+            let from_str = (count_of_fully_contained_pairs(&s), count_of_any_contained_pairs(&s));
+            let from_reader = (
+                count_of_fully_contained_pairs_from_reader(s.as_bytes()),
+                count_of_any_contained_pairs_from_reader(s.as_bytes()),
+            );
+            prop_assert_eq!(format!("{from_str:?}"), format!("{from_reader:?}"));
+        }
+    }
+}
@@ -1,99 +1,42 @@
-#![feature(array_try_map)]
-
-use itertools::Itertools;
+use aoc_common::{DayArgs, Solution};
+use day_04::{count_of_any_contained_pairs, count_of_fully_contained_pairs, Day04};
 use snafu::prelude::*;
-use std::{num::ParseIntError, ops::RangeInclusive};
-
-const INPUT: &str = include_str!("../input");
 
-type Id = u32;
-type Elf = RangeInclusive<Id>;
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
 
 #[snafu::report]
 fn main() -> Result<()> {
-    let part1 = count_of_fully_contained_pairs(INPUT)?;
-    println!("{part1}");
-    assert_eq!(444, part1);
-
-    let part2 = count_of_any_contained_pairs(INPUT)?;
-    println!("{part2}");
-    assert_eq!(801, part2);
-
-    Ok(())
+    let args = DayArgs::from_env();
+    let result = run(&args);
+    args.report_failure(Day04::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
 }
 
-fn count_of_fully_contained_pairs(s: &str) -> Result<usize> {
-    let pairs = parse_elf_pairs(s).filter_ok(|[e1, e2]| either_fully_contains(e1, e2));
+fn run(args: &DayArgs) -> Result<()> {
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
+    let mut output = args
+        .output(Day04::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
 
-    itertools::process_results(pairs, |i| i.count())
-}
+    output.solve(1, || count_of_fully_contained_pairs(&input))?;
 
-fn count_of_any_contained_pairs(s: &str) -> Result<usize> {
-    let pairs = parse_elf_pairs(s).filter_ok(|[e1, e2]| either_any_contains(e1, e2));
+    output.solve(2, || count_of_any_contained_pairs(&input))?;
 
-    itertools::process_results(pairs, |i| i.count())
-}
+    output.finish().context(AnswersSnafu)?;
 
-fn parse_elf_pairs(s: &str) -> impl Iterator<Item = Result<[Elf; 2]>> + '_ {
-    s.lines().map(|l| {
-        let (e1, e2) = l.split_once(',').context(MissingPairSnafu)?;
-        let [e1, e2] = [e1, e2].try_map(|e| e.split_once('-').context(MissingRangePartSnafu))?;
-        [e1, e2].try_map(|(s, e)| {
-            let [s, e] = [s, e].try_map(|id| id.parse::<Id>().context(InvalidIdSnafu { id }))?;
-            Ok(s..=e)
-        })
-    })
-}
-
-fn either_fully_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord,
-{
-    fully_contains(a, b) || fully_contains(b, a)
-}
-
-fn fully_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord,
-{
-    a.start() >= b.start() && a.end() <= b.end()
-}
-
-fn either_any_contains<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord,
-{
-    a.contains(b.start()) || a.contains(b.end()) || b.contains(a.start()) || b.contains(a.end())
+    Ok(())
 }
 
 #[derive(Debug, Snafu)]
 enum Error {
-    MissingPair,
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-    MissingRangePart,
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    InvalidId { source: ParseIntError, id: String },
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_04::Error },
 }
 
 type Result<T, E = Error> = std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = include_str!("../input.test");
-
-    #[test]
-    #[snafu::report]
-    fn example() -> Result<()> {
-        assert_eq!(2, count_of_fully_contained_pairs(INPUT)?);
-        Ok(())
-    }
-
-    #[test]
-    #[snafu::report]
-    fn example_part2() -> Result<()> {
-        assert_eq!(4, count_of_any_contained_pairs(INPUT)?);
-        Ok(())
-    }
-}
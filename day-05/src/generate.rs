@@ -0,0 +1,54 @@
+use crate::Day05;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::fmt::Write;
+
+const N_COLUMNS: usize = 9;
+
+/// `size` is the number of moves.
+impl Generate for Day05 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut heights: Vec<usize> = (0..N_COLUMNS).map(|_| rng.gen_range(1..=8)).collect();
+        let tallest = heights.iter().copied().max().unwrap_or(0);
+
+        let mut s = String::new();
+
+        for level in (0..tallest).rev() {
+            let row: Vec<_> = heights
+                .iter()
+                .map(|&h| {
+                    if level < h {
+                        format!("[{}]", rng.gen_range('A'..='Z'))
+                    } else {
+                        "   ".into()
+                    }
+                })
+                .collect();
+            writeln!(s, "{}", row.join(" ")).expect("Unable to write to a string");
+        }
+
+        let labels: Vec<_> = (1..=N_COLUMNS).map(|c| format!(" {c} ")).collect();
+        writeln!(s, "{}", labels.join(" ")).expect("Unable to write to a string");
+        s.push('\n');
+
+        // The moves only depend on the heights of the columns, which
+        // are the same for both cranes.
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.gen_range(0..N_COLUMNS);
+                if heights[from] != 0 {
+                    break from;
+                }
+            };
+            let to = (from + rng.gen_range(1..N_COLUMNS)) % N_COLUMNS;
+            let count = rng.gen_range(1..=heights[from]);
+
+            heights[from] -= count;
+            heights[to] += count;
+
+            writeln!(s, "move {count} from {} to {}", from + 1, to + 1)
+                .expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
@@ -0,0 +1,309 @@
+//! Day 5: Supply Stacks
+//!
+//! The input draws stacks of crates followed by the moves a crane
+//! makes between them. Part 1's crane moves one crate at a time; part
+//! 2's moves several at once, keeping their order. Both report the
+//! crate on top of each stack.
+
+use aoc_common::{diagnostic::end_of, LocateExt, Located, Solution, WithinExt};
+use core::{fmt, str::FromStr};
+use itertools::Either;
+use snafu::prelude::*;
+
+mod generate;
+
+/// The parsed input is the starting [`Cargo`].
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Cargo;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = String;
+
+    type Part2Params = ();
+    type Part2 = String;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        Cargo::parse(s)
+    }
+
+    fn part1(cargo: &Self::Input<'_>, (): ()) -> Result<String> {
+        top_crates_common(cargo, true)
+    }
+
+    fn part2(cargo: &Self::Input<'_>, (): ()) -> Result<String> {
+        top_crates_common(cargo, false)
+    }
+}
+
+/// The top crates after moving them one at a time.
+pub fn top_crates_9000(s: &str) -> Result<String> {
+    Day05::part1(&Day05::parse(s)?, ())
+}
+
+/// The top crates after moving them several at once.
+pub fn top_crates_9001(s: &str) -> Result<String> {
+    Day05::part2(&Day05::parse(s)?, ())
+}
+
+/// The starting stacks of crates and the moves to perform on them.
+#[derive(Debug)]
+pub struct Cargo {
+    columns: Vec<Vec<u8>>,
+    commands: Vec<Command>,
+}
+
+impl Cargo {
+    fn parse(s: &str) -> Result<Self> {
+        let mut lines = s.lines();
+
+        let column_lines = lines.by_ref().take_while(|l| !l.is_empty());
+        let mut columns = vec![];
+
+        for l in column_lines {
+            let row = l
+                .as_bytes()
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.is_ascii_uppercase())
+                .map(|(i, &b)| {
+                    // Each column is four characters wide: `[A] `
+                    let column = i / 4;
+                    (column, b)
+                });
+
+            for (column, b) in row {
+                let column_count = column + 1;
+                if columns.len() < column_count {
+                    columns.resize_with(column_count, Vec::new);
+                }
+                columns[column].push(b);
+            }
+        }
+
+        // Note: We've pushed into the columns from the top-down, so we
+        // need to flip once all input is read.
+        for c in &mut columns {
+            c.reverse();
+        }
+
+        let commands = lines
+            .map(|l| l.parse().within(s, l))
+            .collect::<Result<_, _>>()
+            .context(CommandInvalidSnafu)?;
+
+        Ok(Self { columns, commands })
+    }
+}
+
+fn top_crates_common(cargo: &Cargo, reverse: bool) -> Result<String> {
+    let mut columns = cargo.columns.clone();
+
+    for &Command { count, from, to } in &cargo.commands {
+        let column = from;
+        let [from, to] = columns
+            .get_disjoint_mut([from, to])
+            .context(ColumnsInvalidSnafu)?;
+
+        let available = from.len();
+        let start = available.checked_sub(count).context(NotEnoughCratesSnafu {
+            count,
+            column,
+            available,
+        })?;
+        let removed = from.drain(start..);
+
+        let removed = if reverse {
+            Either::Left(removed.rev())
+        } else {
+            Either::Right(removed)
+        };
+
+        to.extend(removed)
+    }
+
+    Ok(columns
+        .iter()
+        .flat_map(|c| c.last())
+        .map(|&b| char::from(b))
+        .collect())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Command {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+impl FromStr for Command {
+    type Err = Located<CommandError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use command_error::*;
+
+        let mut parts = s.split_whitespace().fuse();
+
+        let count = parts
+            .nth(1)
+            .context(MissingCountSnafu)
+            .locate(s, end_of(s))?;
+        let count = count.parse().context(InvalidCountSnafu).locate(s, count)?;
+
+        let from = parts
+            .nth(1)
+            .context(MissingFromSnafu)
+            .locate(s, end_of(s))?;
+        // Note: We zero-index the columns, but the input is one-indexed
+        let from = from
+            .parse::<usize>()
+            .context(InvalidFromSnafu)
+            .and_then(|v| v.checked_sub(1).context(ZeroFromSnafu))
+            .locate(s, from)?;
+
+        let to = parts.nth(1).context(MissingToSnafu).locate(s, end_of(s))?;
+        let to = to
+            .parse::<usize>()
+            .context(InvalidToSnafu)
+            .and_then(|v| v.checked_sub(1).context(ZeroToSnafu))
+            .locate(s, to)?;
+
+        Ok(Command { count, from, to })
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { count, from, to } = self;
+        write!(f, "move {count} from {} to {}", from + 1, to + 1)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    CommandInvalid {
+        source: Located<CommandError>,
+    },
+
+    ColumnsInvalid {
+        source: std::slice::GetDisjointMutError,
+    },
+
+    #[snafu(display(
+        "Cannot move {count} crates from column {} as it only has {available}",
+        column + 1,
+    ))]
+    NotEnoughCrates {
+        count: usize,
+        column: usize,
+        available: usize,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum CommandError {
+    MissingCount,
+    InvalidCount { source: std::num::ParseIntError },
+
+    MissingFrom,
+    InvalidFrom { source: std::num::ParseIntError },
+    ZeroFrom,
+
+    MissingTo,
+    InvalidTo { source: std::num::ParseIntError },
+    ZeroTo,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!("CMZ", top_crates_9000(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!("MCD", top_crates_9001(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    fn command_errors_point_at_the_problem() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from x to 1\n";
+
+        let Err(Error::CommandInvalid { source }) = Day05::parse(input) else {
+            panic!("The command should have been rejected");
+        };
+
+        assert_eq!(5, source.location.line);
+        assert_eq!(13, source.location.column);
+        assert!(matches!(source.source, CommandError::InvalidFrom { .. }));
+    }
+
+    #[test]
+    fn column_zero_is_rejected() {
+        let input = "[A]\n 1 \n\nmove 1 from 0 to 1\n";
+
+        let Err(Error::CommandInvalid { source }) = Day05::parse(input) else {
+            panic!("The command should have been rejected");
+        };
+
+        assert_eq!(13, source.location.column);
+        assert!(matches!(source.source, CommandError::ZeroFrom));
+    }
+
+    #[test]
+    fn moving_too_many_crates_is_rejected() -> Result<()> {
+        let cargo = Day05::parse("[A] [B]\n 1   2 \n\nmove 2 from 1 to 2\n")?;
+
+        assert!(matches!(
+            top_crates_common(&cargo, true),
+            Err(Error::NotEnoughCrates {
+                count: 2,
+                column: 0,
+                available: 1,
+            }),
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day05>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"(( {0,4}\[[A-Z]\])*\n){0,4}( [0-9] )*\n\n(move [0-9]{1,2} from [0-9] to [0-9x]\n){0,10}") {
+            if let Ok(parsed) = Day05::parse(&s) {
+                let _ = Day05::part1(&parsed, Day05::PART1_PARAMS);
+                let _ = Day05::part2(&parsed, Day05::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn command_round_trips(count: usize, from in 0..usize::MAX, to in 0..usize::MAX) {
+            let command = Command { count, from, to };
+            prop_assert_eq!(Some(command), command.to_string().parse().ok());
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::Day06;
+use aoc_common::generate::{Generate, GenerateRng, SliceRandom};
+
+/// `size` is the number of characters before the markers can be found.
+impl Generate for Day06 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut letters: Vec<_> = ('a'..='z').collect();
+        letters.shuffle(rng);
+
+        let mut s = String::with_capacity(size + 15);
+
+        // Too few distinct letters to form a start-of-packet marker...
+        s.extend((0..size / 2).map(|_| letters[..3].choose(rng).expect("Not empty")));
+        // ...or a start-of-message marker...
+        s.extend((size / 2..size).map(|_| letters[..13].choose(rng).expect("Not empty")));
+        // ...until all of them are used.
+        s.extend(&letters[..14]);
+        s.push('\n');
+
+        s
+    }
+}
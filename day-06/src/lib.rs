@@ -0,0 +1,123 @@
+//! Day 6: Tuning Trouble
+//!
+//! The input is a stream of characters. Part 1 finds where the first
+//! four distinct characters in a row end; part 2 does the same for
+//! fourteen.
+
+use aoc_common::Solution;
+use itertools::Itertools;
+use snafu::prelude::*;
+
+mod generate;
+
+/// The parsed input is the stream itself.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = &'a [u8];
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        Ok(s.trim().as_bytes())
+    }
+
+    fn part1(signal: &Self::Input<'_>, (): ()) -> Result<usize> {
+        common(signal, 4)
+    }
+
+    fn part2(signal: &Self::Input<'_>, (): ()) -> Result<usize> {
+        common(signal, 14)
+    }
+}
+
+/// How many characters are read before the first start-of-packet marker is complete.
+pub fn location_of_first_start_of_packet(s: &str) -> Result<usize> {
+    Day06::part1(&Day06::parse(s)?, ())
+}
+
+/// How many characters are read before the first start-of-message marker is complete.
+pub fn location_of_first_start_of_message(s: &str) -> Result<usize> {
+    Day06::part2(&Day06::parse(s)?, ())
+}
+
+fn common(signal: &[u8], width: usize) -> Result<usize> {
+    signal
+        .windows(width)
+        .enumerate()
+        .find_map(|(i, w)| w.iter().all_unique().then_some(i + width))
+        .context(NoStartFoundSnafu)
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    NoStartFound,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUTS: &[(&str, usize, usize)] = &[
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        for (i, &(input, location, _)) in INPUTS.iter().enumerate() {
+            assert_eq!(
+                location,
+                location_of_first_start_of_packet(input)?,
+                "Test input {i} ({input:?}) failed"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        for (i, &(input, _, location)) in INPUTS.iter().enumerate() {
+            assert_eq!(
+                location,
+                location_of_first_start_of_message(input)?,
+                "Test input {i} ({input:?}) failed"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day06>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"[a-z]{0,30}") {
+            if let Ok(parsed) = Day06::parse(&s) {
+                let _ = Day06::part1(&parsed, Day06::PART1_PARAMS);
+                let _ = Day06::part2(&parsed, Day06::PART2_PARAMS);
+            }
+        }
+    }
+}
@@ -1,80 +1,42 @@
-use itertools::Itertools;
+use aoc_common::{DayArgs, Solution};
+use day_06::{location_of_first_start_of_message, location_of_first_start_of_packet, Day06};
 use snafu::prelude::*;
 
-const INPUT: &str = include_str!("../input");
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
 
 #[snafu::report]
 fn main() -> Result<()> {
-    let part1 = location_of_first_start_of_packet(INPUT)?;
-    println!("{part1}");
-    assert_eq!(1282, part1);
+    let args = DayArgs::from_env();
+    let result = run(&args);
+    args.report_failure(Day06::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
+}
 
-    let part2 = location_of_first_start_of_message(INPUT)?;
-    println!("{part2}");
-    assert_eq!(3513, part2);
+fn run(args: &DayArgs) -> Result<()> {
+    let input = args.read_input(INPUT_PATH).context(InputSnafu)?;
+    let mut output = args
+        .output(Day06::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
 
-    Ok(())
-}
+    output.solve(1, || location_of_first_start_of_packet(&input))?;
 
-fn location_of_first_start_of_packet(s: &str) -> Result<usize> {
-    common(s, 4)
-}
+    output.solve(2, || location_of_first_start_of_message(&input))?;
 
-fn location_of_first_start_of_message(s: &str) -> Result<usize> {
-    common(s, 14)
-}
+    output.finish().context(AnswersSnafu)?;
 
-fn common(s: &str, width: usize) -> Result<usize> {
-    s.trim()
-        .as_bytes()
-        .windows(width)
-        .enumerate()
-        .find_map(|(i, w)| w.iter().all_unique().then_some(i + width))
-        .context(NoStartFoundSnafu)
+    Ok(())
 }
 
 #[derive(Debug, Snafu)]
 enum Error {
-    NoStartFound,
-}
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-type Result<T, E = Error> = std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUTS: &[(&str, usize, usize)] = &[
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
-    ];
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    #[test]
-    #[snafu::report]
-    fn example() -> Result<()> {
-        for (i, &(input, location, _)) in INPUTS.into_iter().enumerate() {
-            assert_eq!(
-                location,
-                location_of_first_start_of_packet(input)?,
-                "Test input {i} ({input:?}) failed"
-            );
-        }
-        Ok(())
-    }
-
-    #[test]
-    #[snafu::report]
-    fn example_part2() -> Result<()> {
-        for (i, &(input, _, location)) in INPUTS.into_iter().enumerate() {
-            assert_eq!(
-                location,
-                location_of_first_start_of_message(input)?,
-                "Test input {i} ({input:?}) failed"
-            );
-        }
-        Ok(())
-    }
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_06::Error },
 }
+
+type Result<T, E = Error> = std::result::Result<T, E>;
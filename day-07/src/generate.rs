@@ -0,0 +1,91 @@
+use crate::{Day07, TOTAL_DISK_SIZE, UPDATE_SIZE};
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::{collections::BTreeSet, fmt::Write};
+
+#[derive(Default)]
+struct Node {
+    name: String,
+    files: Vec<(String, u64)>,
+    children: Vec<usize>,
+    names: BTreeSet<String>,
+}
+
+impl Node {
+    fn unique_name(&mut self, rng: &mut GenerateRng, extension: bool) -> String {
+        loop {
+            let len = rng.gen_range(1..=8);
+            let mut name: String = (0..len).map(|_| rng.gen_range('a'..='z')).collect();
+            if extension && rng.gen() {
+                name.push('.');
+                name.extend((0..3).map(|_| rng.gen_range('a'..='z')));
+            }
+
+            if self.names.insert(name.clone()) {
+                break name;
+            }
+        }
+    }
+}
+
+/// `size` is the number of directories, other than the root.
+impl Generate for Day07 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut nodes = vec![Node {
+            name: "/".into(),
+            ..Node::default()
+        }];
+
+        for i in 1..=size {
+            let parent = rng.gen_range(0..i);
+            let name = nodes[parent].unique_name(rng, false);
+            nodes[parent].children.push(i);
+            nodes.push(Node {
+                name,
+                ..Node::default()
+            });
+        }
+
+        // Part 2 requires that the update doesn't fit without deleting
+        // something, but that the disk isn't overfull.
+        let used = rng.gen_range(TOTAL_DISK_SIZE - UPDATE_SIZE + 1..=TOTAL_DISK_SIZE);
+        let n_files = nodes.len() * 2;
+        let largest_file = (used / 2 / n_files as u64).clamp(1, 300_000);
+
+        let mut total = 0;
+        for node in &mut nodes {
+            for _ in 0..rng.gen_range(0..=4) {
+                let name = node.unique_name(rng, true);
+                let file_size = rng.gen_range(1..=largest_file);
+                node.files.push((name, file_size));
+                total += file_size;
+            }
+        }
+
+        let root = &mut nodes[0];
+        let name = root.unique_name(rng, true);
+        root.files.push((name, used - total));
+
+        let mut s = String::new();
+        s.push_str("$ cd /\n");
+        session(&nodes, 0, &mut s);
+        s
+    }
+}
+
+fn session(nodes: &[Node], index: usize, s: &mut String) {
+    let node = &nodes[index];
+
+    s.push_str("$ ls\n");
+    for &child in &node.children {
+        writeln!(s, "dir {}", nodes[child].name).expect("Unable to write to a string");
+    }
+    for (name, size) in &node.files {
+        writeln!(s, "{size} {name}").expect("Unable to write to a string");
+    }
+
+    for &child in &node.children {
+        writeln!(s, "$ cd {}", nodes[child].name).expect("Unable to write to a string");
+        session(nodes, child, s);
+        s.push_str("$ cd ..\n");
+    }
+}
@@ -0,0 +1,343 @@
+//! Day 7: No Space Left On Device
+//!
+//! The input is a terminal session of `cd` and `ls` commands that
+//! explores a filesystem. Part 1 sums the sizes of the small
+//! directories; part 2 finds the smallest directory that frees enough
+//! space for an update.
+
+use aoc_common::{diagnostic::end_of, LocateExt, Located, Solution, WithinExt};
+use snafu::prelude::*;
+use std::{collections::BTreeMap, fmt};
+
+mod generate;
+
+const TOTAL_DISK_SIZE: u64 = 70_000_000;
+const UPDATE_SIZE: u64 = 30_000_000;
+
+/// The parsed input is the root [`Directory`].
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Directory<'a>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = u64;
+
+    type Part2Params = ();
+    type Part2 = u64;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        build_directory_hierarchy(s)
+    }
+
+    fn part1(root: &Self::Input<'_>, (): ()) -> Result<u64> {
+        let total = root
+            .directories()
+            .map(|d| d.total_size())
+            .filter(|&s| s <= 100_000)
+            .sum::<u64>();
+
+        Ok(total)
+    }
+
+    fn part2(root: &Self::Input<'_>, (): ()) -> Result<u64> {
+        let used_size = root.total_size();
+        let free_size = TOTAL_DISK_SIZE
+            .checked_sub(used_size)
+            .context(DiskOverfullSnafu { used_size })?;
+        let additional_size = UPDATE_SIZE
+            .checked_sub(free_size)
+            .context(UpdateAlreadyFitsSnafu { free_size })?;
+
+        root.directories()
+            .map(|d| d.total_size())
+            .filter(|&s| s >= additional_size)
+            .min()
+            .context(NoAvailableDirectorySnafu)
+    }
+}
+
+/// The summed sizes of every directory no larger than 100000.
+pub fn sum_of_directories_less_than_100000(s: &str) -> Result<u64> {
+    Day07::part1(&Day07::parse(s)?, ())
+}
+
+/// The size of the smallest directory whose removal frees enough space for the update.
+pub fn size_of_smallest_directory_to_allow_update(s: &str) -> Result<u64> {
+    Day07::part2(&Day07::parse(s)?, ())
+}
+
+fn build_directory_hierarchy(s: &str) -> Result<Directory<'_>> {
+    let mut cursor = Directory::new("/");
+
+    for l in s.lines() {
+        let l = Line::try_from(l).within(s, l).context(LineInvalidSnafu)?;
+
+        match l {
+            Line::ChangeDirectory("/") => cursor = cursor.into_root(),
+            Line::ChangeDirectory("..") => cursor = cursor.into_parent(),
+            Line::ChangeDirectory(name) => cursor = cursor.into_child(name),
+            Line::List => {}
+            Line::DirEntry(name) => cursor.add_directory(name),
+            Line::FileEntry(name, size) => cursor.add_file(name, size),
+        }
+    }
+
+    Ok(cursor.into_root())
+}
+
+/// A directory and everything below it, as discovered by the terminal
+/// session.
+///
+/// ```
+/// use aoc_common::Solution;
+/// use day_07::Day07;
+///
+/// let root = Day07::parse("$ cd /\n$ ls\ndir a\n100 b.txt\n$ cd a\n$ ls\n20 c\n")?;
+///
+/// let sizes: Vec<_> = root.directories().map(|d| (d.name(), d.total_size())).collect();
+/// assert_eq!([("/", 120), ("a", 20)], sizes[..]);
+/// # Ok::<_, day_07::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Directory<'a> {
+    name: &'a str,
+    parent: Option<Box<Self>>,
+    directories: BTreeMap<&'a str, Self>,
+    files: BTreeMap<&'a str, u64>,
+}
+
+impl<'a> Directory<'a> {
+    fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    fn into_root(self) -> Directory<'a> {
+        let mut current = self;
+        while let Some(mut parent) = current.parent.take() {
+            parent.directories.insert(current.name, current);
+            current = *parent
+        }
+        current
+    }
+
+    fn into_parent(self) -> Directory<'a> {
+        let mut current = self;
+        if let Some(mut parent) = current.parent.take() {
+            parent.directories.insert(current.name, current);
+            current = *parent
+        }
+        current
+    }
+
+    fn into_child(mut self, name: &'a str) -> Directory<'a> {
+        let mut child = self
+            .directories
+            .remove(name)
+            .unwrap_or_else(|| Self::new(name));
+        child.parent = Some(Box::new(self));
+        child
+    }
+
+    fn add_directory(&mut self, name: &'a str) {
+        self.directories
+            .entry(name)
+            .or_insert_with(|| Self::new(name));
+    }
+
+    fn add_file(&mut self, name: &'a str, size: u64) {
+        self.files.insert(name, size);
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The name and size of each file directly in this directory.
+    pub fn files(&self) -> impl Iterator<Item = (&'a str, u64)> + '_ {
+        self.files.iter().map(|(&name, &size)| (name, size))
+    }
+
+    /// This directory followed by every directory below it.
+    pub fn directories(&self) -> impl Iterator<Item = &'_ Directory<'a>> + '_ {
+        let mut state = vec![self];
+
+        std::iter::from_fn(move || {
+            let top = state.pop()?;
+            state.extend(top.directories.values());
+            Some(top)
+        })
+    }
+
+    /// The size of every file in this directory and below.
+    pub fn total_size(&self) -> u64 {
+        let files = self.files.values().sum::<u64>();
+        let children = self.directories.values().map(Self::total_size).sum::<u64>();
+
+        files + children
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Line<'a> {
+    ChangeDirectory(&'a str),
+    List,
+    DirEntry(&'a str),
+    FileEntry(&'a str, u64),
+}
+
+impl<'a> TryFrom<&'a str> for Line<'a> {
+    type Error = Located<LineError>;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        use line_error::*;
+
+        let missing = end_of(value);
+        let mut parts = value.split_whitespace();
+        Ok(match parts.next() {
+            Some("$") => match parts.next() {
+                Some("cd") => {
+                    let name = parts
+                        .next()
+                        .context(MissingChangeDirectoryNameSnafu)
+                        .locate(value, missing)?;
+                    Line::ChangeDirectory(name)
+                }
+                Some("ls") => Line::List,
+                Some(cmd) => return UnknownCommandSnafu { cmd }.fail().locate(value, cmd),
+                None => return MissingCommandSnafu.fail().locate(value, missing),
+            },
+            Some("dir") => {
+                let name = parts
+                    .next()
+                    .context(MissingDirectoryEntryNameSnafu)
+                    .locate(value, missing)?;
+                Line::DirEntry(name)
+            }
+            Some(size) => {
+                let size = size
+                    .parse()
+                    .context(InvalidFileEntrySizeSnafu)
+                    .locate(value, size)?;
+                let name = parts
+                    .next()
+                    .context(MissingFileEntryNameSnafu)
+                    .locate(value, missing)?;
+                Line::FileEntry(name, size)
+            }
+            None => return MissingOutputSnafu.fail().locate(value, missing),
+        })
+    }
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Line::ChangeDirectory(name) => write!(f, "$ cd {name}"),
+            Line::List => write!(f, "$ ls"),
+            Line::DirEntry(name) => write!(f, "dir {name}"),
+            Line::FileEntry(name, size) => write!(f, "{size} {name}"),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    LineInvalid {
+        source: Located<LineError>,
+    },
+
+    NoAvailableDirectory,
+
+    #[snafu(display("{used_size} bytes are used but the disk only holds {TOTAL_DISK_SIZE}"))]
+    DiskOverfull {
+        used_size: u64,
+    },
+
+    #[snafu(display("{free_size} bytes are already free, enough for the update"))]
+    UpdateAlreadyFits {
+        free_size: u64,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum LineError {
+    MissingChangeDirectoryName,
+
+    UnknownCommand { cmd: String },
+
+    MissingCommand,
+
+    MissingDirectoryEntryName,
+
+    InvalidFileEntrySize { source: std::num::ParseIntError },
+
+    MissingFileEntryName,
+
+    MissingOutput,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(95437, sum_of_directories_less_than_100000(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(24933642, size_of_smallest_directory_to_allow_update(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day07>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"(\$ cd (/|\.\.|[a-c])\n|\$ ls\n|\$ rm\n|dir [a-c]\n|[0-9]{1,9} [a-c]\n){0,15}") {
+            if let Ok(parsed) = Day07::parse(&s) {
+                let _ = Day07::part1(&parsed, Day07::PART1_PARAMS);
+                let _ = Day07::part2(&parsed, Day07::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn line_round_trips(name in "[a-z./]{1,10}", size: u64) {
+            let lines = [
+                Line::ChangeDirectory(&name),
+                Line::List,
+                Line::DirEntry(&name),
+                Line::FileEntry(&name, size),
+            ];
+
+            for line in lines {
+                let s = line.to_string();
+                prop_assert_eq!(Some(line), Line::try_from(&*s).ok());
+            }
+        }
+    }
+}
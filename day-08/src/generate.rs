@@ -0,0 +1,18 @@
+use crate::Day08;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+
+/// `size` is the width and height of the forest, which is at least 3
+/// so that it has an interior.
+impl Generate for Day08 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let size = size.max(3);
+        let mut s = String::with_capacity((size + 1) * size);
+
+        for _ in 0..size {
+            s.extend((0..size).map(|_| rng.gen_range('0'..='9')));
+            s.push('\n');
+        }
+
+        s
+    }
+}
@@ -0,0 +1,234 @@
+//! Day 8: Treetop Tree House
+//!
+//! The input is a grid of tree heights. Part 1 counts the trees
+//! visible from outside the grid; part 2 finds the best scenic score,
+//! the product of how far a tree can see in each direction.
+
+use aoc_common::{
+    visualize::frame_from_grid, BoundingBox, Frame, Grid, Located, Palette, ParseGridError, Point,
+    Solution, Style,
+};
+use snafu::prelude::*;
+use std::{collections::BTreeSet, ops::ControlFlow};
+
+mod generate;
+
+/// The parsed input is the [`Forest`].
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Forest;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_forest(s)
+    }
+
+    fn part1(forest: &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(visible(forest)?.len())
+    }
+
+    fn part2(forest: &Self::Input<'_>, (): ()) -> Result<usize> {
+        let bounds = forest.bounds().context(ForestIsEmptySnafu)?;
+        let BoundingBox {
+            min: Point { x: min_x, y: min_y },
+            max: Point { x: max_x, y: max_y },
+        } = bounds;
+        let height_at = |x, y| forest[Point::new(x, y)];
+
+        // Trees on the edge see nothing in one direction, so their
+        // score is zero; this keeps single-row forests working.
+        bounds
+            .points()
+            .map(|Point { x, y }| {
+                let my_height = height_at(x, y);
+
+                let top = (min_y..y)
+                    .rev()
+                    .count_trees(|&y| height_at(x, y) < my_height);
+                let bottom = (y + 1..=max_y).count_trees(|&y| height_at(x, y) < my_height);
+                let left = (min_x..x)
+                    .rev()
+                    .count_trees(|&x| height_at(x, y) < my_height);
+                let right = (x + 1..=max_x).count_trees(|&x| height_at(x, y) < my_height);
+
+                top * bottom * left * right
+            })
+            .max()
+            .context(ForestIsEmptySnafu)
+    }
+}
+
+/// How many trees can be seen from outside the forest.
+pub fn visible_trees(s: &str) -> Result<usize> {
+    Day08::part1(&Day08::parse(s)?, ())
+}
+
+/// As [`visible_trees`], calling `f` with the forest and the trees
+/// that can be seen.
+pub fn visible_trees_with(s: &str, f: impl FnOnce(&Forest, &BTreeSet<Coord>)) -> Result<usize> {
+    let forest = Day08::parse(s)?;
+    let visible = visible(&forest)?;
+    f(&forest, &visible);
+    Ok(visible.len())
+}
+
+/// The highest scenic score of any tree.
+pub fn maximum_scenic_score(s: &str) -> Result<usize> {
+    Day08::part2(&Day08::parse(s)?, ())
+}
+
+fn visible(forest: &Forest) -> Result<BTreeSet<Coord>> {
+    let bounds = forest.bounds().context(ForestIsEmptySnafu)?;
+    let BoundingBox {
+        min: Point { x: min_x, y: min_y },
+        max: Point { x: max_x, y: max_y },
+    } = bounds;
+    let height_at = |x, y| forest[Point::new(x, y)];
+
+    let visible = bounds
+        .points()
+        .filter(|&Point { x, y }| {
+            let my_height = height_at(x, y);
+
+            let visible_from_top = (min_y..y).rev().all(|y| height_at(x, y) < my_height);
+            let visible_from_bottom = (y + 1..=max_y).all(|y| height_at(x, y) < my_height);
+            let visible_from_left = (min_x..x).rev().all(|x| height_at(x, y) < my_height);
+            let visible_from_right = (x + 1..=max_x).all(|x| height_at(x, y) < my_height);
+
+            visible_from_top || visible_from_bottom || visible_from_left || visible_from_right
+        })
+        .collect();
+
+    Ok(visible)
+}
+
+/// Whether a tree can be seen from outside the forest, for drawing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Visibility {
+    Visible,
+    Hidden,
+}
+
+/// Visible trees are bright against the darker hidden ones.
+pub fn palette() -> Palette<Visibility> {
+    Palette::new(Style::new(' ', [0, 0, 0]))
+        .with(Visibility::Visible, Style::new('#', [96, 224, 64]))
+        .with(Visibility::Hidden, Style::new('.', [16, 64, 16]))
+}
+
+/// Every tree of the forest, marked by whether it is in `visible`.
+pub fn visibility_frame(forest: &Forest, visible: &BTreeSet<Coord>) -> Frame<Visibility> {
+    frame_from_grid(forest, |p, _| {
+        Some(if visible.contains(&p) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        })
+    })
+}
+
+/// The position of a tree, from the top-left corner.
+pub type Coord = Point<usize>;
+type Height = u32;
+/// The height of each tree, from 0 to 9.
+pub type Forest = Grid<Height>;
+
+fn parse_forest(s: &str) -> Result<Forest> {
+    Grid::parse(s, |_, c| c.to_digit(10).context(InvalidHeightSnafu { c }))
+        .context(ForestInvalidSnafu)
+}
+
+trait ForestExt: Iterator {
+    fn count_trees<P>(self, pred: P) -> usize
+    where
+        P: FnMut(&Self::Item) -> bool;
+}
+
+impl<I> ForestExt for I
+where
+    I: Iterator,
+{
+    fn count_trees<P>(mut self, mut pred: P) -> usize
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let visible = self.try_fold(0, |acc, y| {
+            if pred(&y) {
+                ControlFlow::Continue(acc + 1)
+            } else {
+                ControlFlow::Break(acc)
+            }
+        });
+
+        match visible {
+            ControlFlow::Continue(v) => v,  // ran out of forest
+            ControlFlow::Break(v) => v + 1, // hit a tree
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct InvalidHeightError {
+    c: char,
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    ForestInvalid {
+        source: Located<ParseGridError<InvalidHeightError>>,
+    },
+
+    ForestIsEmpty,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(21, visible_trees(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(8, maximum_scenic_score(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day08>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"([0-9]{0,5}\n){0,5}") {
+            if let Ok(parsed) = Day08::parse(&s) {
+                let _ = Day08::part1(&parsed, Day08::PART1_PARAMS);
+                let _ = Day08::part2(&parsed, Day08::PART2_PARAMS);
+            }
+        }
+    }
+}
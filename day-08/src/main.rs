@@ -1,149 +1,57 @@
-use itertools::Itertools;
+use aoc_common::{Solution, VisualDayArgs};
+use day_08::{maximum_scenic_score, visibility_frame, visible_trees_with, Day08};
 use snafu::prelude::*;
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    ops::ControlFlow,
-};
 
-const INPUT: &str = include_str!("../input");
+const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
 
 #[snafu::report]
 fn main() -> Result<()> {
-    let part1 = visible_trees(INPUT)?;
-    println!("{part1}");
-    assert_eq!(1792, part1);
-
-    let part2 = maximum_scenic_score(INPUT)?;
-    println!("{part2}");
-    assert_eq!(334880, part2);
-
-    Ok(())
-}
-
-fn visible_trees(s: &str) -> Result<usize> {
-    let forest = parse_forest(s)?;
-    let ((min_x, min_y), (max_x, max_y)) = forest_bounds(&forest).context(ForestIsEmptySnafu)?;
-
-    let all_coords = (min_x..=max_x).cartesian_product(min_y..=max_y);
-
-    let visible: BTreeSet<_> = all_coords
-        .filter(|&(x, y)| {
-            let my_height = forest[&(x, y)];
-
-            let visible_from_top = (min_y..y).rev().all(|y| forest[&(x, y)] < my_height);
-            let visible_from_bottom = (y + 1..=max_y).all(|y| forest[&(x, y)] < my_height);
-            let visible_from_left = (min_x..x).rev().all(|x| forest[&(x, y)] < my_height);
-            let visible_from_right = (x + 1..=max_x).all(|x| forest[&(x, y)] < my_height);
-
-            visible_from_top || visible_from_bottom || visible_from_left || visible_from_right
-        })
-        .collect();
-
-    Ok(visible.len())
-}
-
-fn maximum_scenic_score(s: &str) -> Result<usize> {
-    let forest = parse_forest(s)?;
-    let ((min_x, min_y), (max_x, max_y)) = forest_bounds(&forest).context(ForestIsEmptySnafu)?;
-
-    let inner_coords = (min_y + 1..=max_y - 1).cartesian_product(min_x + 1..=max_x - 1);
-
-    inner_coords
-        .map(|(y, x)| {
-            let my_height = forest[&(x, y)];
-
-            let top = (min_y..y)
-                .rev()
-                .count_trees(|&y| forest[&(x, y)] < my_height);
-            let bottom = (y + 1..=max_y).count_trees(|&y| forest[&(x, y)] < my_height);
-            let left = (min_x..x)
-                .rev()
-                .count_trees(|&x| forest[&(x, y)] < my_height);
-            let right = (x + 1..=max_x).count_trees(|&x| forest[&(x, y)] < my_height);
-
-            top * bottom * left * right
-        })
-        .max()
-        .context(ForestIsEmptySnafu)
-}
-
-type Coord = (usize, usize);
-type Height = u32;
-type Forest = BTreeMap<Coord, Height>;
-
-fn parse_forest(s: &str) -> Result<Forest> {
-    s.lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(move |(x, c)| {
-                let height = c.to_digit(10).context(InvalidHeightSnafu { x, y, c })?;
-                Ok(((x, y), height))
-            })
+    let args = VisualDayArgs::from_env();
+    let result = run(&args);
+    args.day
+        .report_failure(Day08::DAY, result, |e| matches!(e, Error::Puzzle { .. }))
+}
+
+fn run(args: &VisualDayArgs) -> Result<()> {
+    let mut recorder = args
+        .visualize
+        .recorder(day_08::palette())
+        .context(VisualizeSnafu)?;
+    let input = args.day.read_input(INPUT_PATH).context(InputSnafu)?;
+    let mut output = args
+        .day
+        .output(Day08::DAY, ANSWERS_PATH, &input)
+        .context(AnswersSnafu)?;
+
+    output.solve(1, || {
+        visible_trees_with(&input, |forest, visible| {
+            recorder.record(|| visibility_frame(forest, visible))
         })
-        .collect()
-}
+    })?;
 
-fn forest_bounds(forest: &Forest) -> Option<(Coord, Coord)> {
-    let min = forest.keys().next().copied()?;
-    let max = forest.keys().next_back().copied()?;
-    Some((min, max))
-}
+    output.solve(2, || maximum_scenic_score(&input))?;
 
-trait ForestExt: Iterator {
-    fn count_trees<P>(self, pred: P) -> usize
-    where
-        P: FnMut(&Self::Item) -> bool;
-}
+    recorder.finish().context(VisualizeSnafu)?;
 
-impl<I> ForestExt for I
-where
-    I: Iterator,
-{
-    fn count_trees<P>(mut self, mut pred: P) -> usize
-    where
-        P: FnMut(&Self::Item) -> bool,
-    {
-        let visible = self.try_fold(0, |acc, y| {
-            if pred(&y) {
-                ControlFlow::Continue(acc + 1)
-            } else {
-                ControlFlow::Break(acc)
-            }
-        });
+    output.finish().context(AnswersSnafu)?;
 
-        match visible {
-            ControlFlow::Continue(v) => v,  // ran out of forest
-            ControlFlow::Break(v) => v + 1, // hit a tree
-        }
-    }
+    Ok(())
 }
 
 #[derive(Debug, Snafu)]
 enum Error {
-    InvalidHeight { x: usize, y: usize, c: char },
-
-    ForestIsEmpty,
-}
-
-type Result<T, E = Error> = std::result::Result<T, E>;
-
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[snafu(display("Could not read the input"))]
+    Input { source: aoc_common::InputError },
 
-    const INPUT: &str = include_str!("../input.test");
+    #[snafu(display("Could not check the answers"))]
+    Answers { source: aoc_common::AnswersError },
 
-    #[test]
-    #[snafu::report]
-    fn example() -> Result<()> {
-        assert_eq!(21, visible_trees(INPUT)?);
-        Ok(())
-    }
+    #[snafu(display("Could not draw the working"))]
+    Visualize { source: aoc_common::VisualizeError },
 
-    #[test]
-    #[snafu::report]
-    fn example_part2() -> Result<()> {
-        assert_eq!(8, maximum_scenic_score(INPUT)?);
-        Ok(())
-    }
+    #[snafu(context(false), display("Could not solve the puzzle"))]
+    Puzzle { source: day_08::Error },
 }
+
+type Result<T, E = Error> = std::result::Result<T, E>;
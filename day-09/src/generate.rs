@@ -0,0 +1,18 @@
+use crate::Day09;
+use aoc_common::generate::{Generate, GenerateRng, Rng, SliceRandom};
+use std::fmt::Write;
+
+/// `size` is the number of moves.
+impl Generate for Day09 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for _ in 0..size {
+            let direction = ["U", "D", "L", "R"].choose(rng).expect("Not empty");
+            let count = rng.gen_range(1..=20);
+            writeln!(s, "{direction} {count}").expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
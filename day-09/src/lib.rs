@@ -0,0 +1,319 @@
+//! Day 9: Rope Bridge
+//!
+//! The input moves the head of a rope around a grid, with each knot
+//! following the one before it. Part 1 counts the positions visited by
+//! the tail of a two-knot rope; part 2 does the same for ten knots.
+
+use aoc_common::{
+    diagnostic::end_of, input::numbered_lines, Direction, Frame, LocateExt, Located, Palette,
+    Point, Solution, Style, WithinExt,
+};
+use snafu::prelude::*;
+use std::{collections::BTreeSet, fmt, io::BufRead, str::FromStr};
+
+mod generate;
+
+/// The parsed input is the list of [`Command`]s.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Command>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        s.lines()
+            .map(|line| {
+                line.parse::<Command>()
+                    .within(s, line)
+                    .context(InvalidCommandSnafu)
+            })
+            .collect()
+    }
+
+    fn part1(commands: &Self::Input<'_>, (): ()) -> Result<usize> {
+        simulate::<2>(commands.iter().copied().map(Ok), |_| {})
+    }
+
+    fn part2(commands: &Self::Input<'_>, (): ()) -> Result<usize> {
+        simulate::<10>(commands.iter().copied().map(Ok), |_| {})
+    }
+}
+
+/// How many positions the tail of an `N`-knot rope visits, calling
+/// `f` with every knot after each command.
+pub fn unique_tail_positions<const N: usize>(s: &str, f: impl FnMut(&[Coord; N])) -> Result<usize> {
+    let commands = Day09::parse(s)?;
+    simulate(commands.into_iter().map(Ok), f)
+}
+
+/// As [`unique_tail_positions`], reading one line at a time.
+pub fn unique_tail_positions_from_reader<const N: usize>(
+    reader: impl BufRead,
+    f: impl FnMut(&[Coord; N]),
+) -> Result<usize> {
+    let commands = numbered_lines(reader).map(|(n, l)| {
+        let l = l.context(ReadSnafu)?;
+        l.parse::<Command>().on_line(n).context(InvalidCommandSnafu)
+    });
+    simulate(commands, f)
+}
+
+/// Moves a rope of `N` knots, calling `f` after each command.
+fn simulate<const N: usize>(
+    commands: impl IntoIterator<Item = Result<Command>>,
+    mut f: impl FnMut(&[Coord; N]),
+) -> Result<usize> {
+    let mut state = State::<N>::default();
+
+    for command in commands {
+        command?.try_repeat(|d| state.move_once(d))?;
+        f(&state.knots);
+    }
+
+    Ok(state.tail_visited())
+}
+
+/// Positions are unbounded in every direction.
+pub type Coord = Point<i32>;
+
+/// The kinds of knot that are drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Knot {
+    Head,
+    Middle,
+    Tail,
+}
+
+/// Green knots on purple.
+pub fn palette() -> Palette<Knot> {
+    Palette::new(Style::new('.', [127, 0, 127]))
+        .with(Knot::Head, Style::new('H', [0, 255, 0]))
+        .with(Knot::Middle, Style::new('#', [0, 191, 0]))
+        .with(Knot::Tail, Style::new('T', [0, 127, 0]))
+}
+
+/// The knots of the rope, for drawing. Where knots overlap, the one
+/// closest to the head is shown.
+pub fn rope_frame<const N: usize>(knots: &[Coord; N]) -> Frame<Knot> {
+    let mut frame = Frame::new();
+
+    for (i, knot) in knots.iter().enumerate().rev() {
+        let kind = match i {
+            0 => Knot::Head,
+            i if i == N - 1 => Knot::Tail,
+            _ => Knot::Middle,
+        };
+        frame.insert(Point::new(knot.x.into(), knot.y.into()), kind);
+    }
+
+    frame
+}
+
+#[derive(Debug)]
+struct State<const N: usize> {
+    knots: [Coord; N],
+    tail_visited: BTreeSet<Coord>,
+}
+
+impl<const N: usize> Default for State<N> {
+    fn default() -> Self {
+        let mut me = Self {
+            knots: [Point::new(0, 0); N],
+            tail_visited: Default::default(),
+        };
+        me.tail_visited.extend(me.knots.last().copied());
+        me
+    }
+}
+
+impl<const N: usize> State<N> {
+    fn tail_visited(&self) -> usize {
+        self.tail_visited.len()
+    }
+
+    fn move_once(&mut self, direction: Direction) -> Result<()> {
+        let indices = 0..N;
+        let head_indices = indices.clone();
+        let tail_indices = indices.skip(1);
+        let head_tail_indices = head_indices.zip(tail_indices);
+
+        if let Some(head) = self.knots.first_mut() {
+            *head = head.step(direction).context(LeftTheBoardSnafu)?;
+        }
+
+        for (head_index, tail_index) in head_tail_indices {
+            let [head, tail] = self
+                .knots
+                .get_disjoint_mut([head_index, tail_index])
+                .expect("Created overlapping indices");
+
+            let delta = head.checked_sub(*tail).context(LeftTheBoardSnafu)?;
+
+            match (delta.x, delta.y) {
+                #[rustfmt::skip]
+                (-1,  1) | ( 0,  1) | ( 1,  1) |
+                (-1,  0) | ( 0,  0) | ( 1,  0) |
+                (-1, -1) | ( 0, -1) | ( 1, -1) => { /* Close enough */ }
+
+                (dx, dy) => {
+                    assert!((-2..=2).contains(&dx));
+                    assert!((-2..=2).contains(&dy));
+
+                    tail.x += dx.signum();
+                    tail.y += dy.signum();
+                }
+            }
+        }
+
+        self.tail_visited.extend(self.knots.last().copied());
+        Ok(())
+    }
+}
+
+/// Moves the head `count` steps in one direction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Command {
+    direction: Direction,
+    count: u8,
+}
+
+impl FromStr for Command {
+    type Err = Located<ParseCommandError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let d = parts
+            .next()
+            .context(MissingDirectionSnafu)
+            .locate(s, end_of(s))?;
+        let count = parts
+            .next()
+            .context(MissingCountSnafu)
+            .locate(s, end_of(s))?;
+        let count = count.parse().context(InvalidCountSnafu).locate(s, count)?;
+        let direction = match d {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            _ => return UnknownDirectionSnafu { d }.fail().locate(s, d),
+        };
+        Ok(Self { direction, count })
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = match self.direction {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        write!(f, "{d} {}", self.count)
+    }
+}
+
+impl Command {
+    fn try_repeat<E>(self, mut f: impl FnMut(Direction) -> Result<(), E>) -> Result<(), E> {
+        (0..self.count).try_for_each(|_| f(self.direction))
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParseCommandError {
+    MissingDirection,
+
+    MissingCount,
+
+    UnknownDirection { d: String },
+
+    InvalidCount { source: std::num::ParseIntError },
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Read { source: std::io::Error },
+
+    InvalidCommand { source: Located<ParseCommandError> },
+
+    LeftTheBoard,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Moves of up to 99 steps, with `X` as a direction that doesn't
+    /// exist.
+    const ARBITRARY_INPUT: &str = r"([UDLRX] [0-9]{1,2}\n){0,10}";
+    const INPUT2: &str = include_str!("../input2.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(13, unique_tail_positions::<2>(INPUT, |_| {})?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(1, unique_tail_positions::<10>(INPUT, |_| {})?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2_input2() -> Result<()> {
+        assert_eq!(36, unique_tail_positions::<10>(INPUT2, |_| {})?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day09>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day09::parse(&s) {
+                let _ = Day09::part1(&parsed, Day09::PART1_PARAMS);
+                let _ = Day09::part2(&parsed, Day09::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_agrees_with_str(s in ARBITRARY_INPUT) {
+            // The reader moves the rope as it reads, so it can leave the
+            // board before reaching a malformed move that parsing finds
+            let from_str = unique_tail_positions::<10>(&s, |_| {});
+            let from_reader = unique_tail_positions_from_reader::<10>(s.as_bytes(), |_| {});
+            prop_assert_eq!(from_str.ok(), from_reader.ok());
+        }
+
+        #[test]
+        fn command_round_trips(direction in prop::sample::select(&Direction::ALL[..]), count: u8) {
+            let command = Command { direction, count };
+            prop_assert_eq!(Some(command), command.to_string().parse().ok());
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{Day10, DISPLAY_WIDTH};
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::fmt::Write;
+
+/// `size` is the number of instructions.
+impl Generate for Day10 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+        let mut x = 1;
+
+        for _ in 0..size {
+            if rng.gen_bool(0.3) {
+                s.push_str("noop\n");
+            } else {
+                // Keep the sprite near the display
+                let target = rng.gen_range(0..i32::from(DISPLAY_WIDTH));
+                let delta = (target - x).clamp(-15, 15);
+                x += delta;
+                writeln!(s, "addx {delta}").expect("Unable to write to a string");
+            }
+        }
+
+        s
+    }
+}
@@ -0,0 +1,305 @@
+//! Day 10: Cathode-Ray Tube
+//!
+//! The input is a program for a simple CPU with one register. Part 1
+//! sums the signal strength at six cycles; part 2 draws the image that
+//! the register traces on the CRT.
+
+use aoc_common::{
+    diagnostic::end_of, input::numbered_lines, LocateExt, Located, Solution, WithinExt,
+};
+use snafu::prelude::*;
+use std::{fmt, io::BufRead, iter, str::FromStr};
+
+mod generate;
+
+const ADD_X_DURATION: u8 = 2;
+
+const DISPLAY_WIDTH: u8 = 40;
+const DISPLAY_HEIGHT: u8 = 6;
+
+/// The parsed input is the list of [`Instruction`]s.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Vec<Instruction>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = i32;
+
+    type Part2Params = ();
+    type Part2 = String;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        s.lines()
+            .map(|l| {
+                Instruction::from_str(l)
+                    .within(s, l)
+                    .context(InstructionInvalidSnafu)
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input<'_>, (): ()) -> Result<i32> {
+        Ok(signal_strengths(instructions.iter().copied()))
+    }
+
+    fn part2(instructions: &Self::Input<'_>, (): ()) -> Result<String> {
+        Ok(render(instructions.iter().copied()))
+    }
+}
+
+/// The summed signal strengths at cycles 20, 60, …, 220.
+pub fn sum_of_six_signal_strengths(s: &str) -> Result<i32> {
+    Day10::part1(&Day10::parse(s)?, ())
+}
+
+/// The CRT image, one line per row, `#` for lit pixels.
+pub fn render_signal(s: &str) -> Result<String> {
+    Day10::part2(&Day10::parse(s)?, ())
+}
+
+/// As [`sum_of_six_signal_strengths`], reading one line at a time and
+/// only as many as are needed.
+pub fn sum_of_six_signal_strengths_from_reader(reader: impl BufRead) -> Result<i32> {
+    itertools::process_results(instructions_from_reader(reader), |i| signal_strengths(i))
+}
+
+/// As [`render_signal`], reading one line at a time and only as many as
+/// are needed.
+pub fn render_signal_from_reader(reader: impl BufRead) -> Result<String> {
+    itertools::process_results(instructions_from_reader(reader), |i| render(i))
+}
+
+fn instructions_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Instruction>> {
+    numbered_lines(reader).map(|(n, l)| {
+        let l = l.context(ReadSnafu)?;
+        Instruction::from_str(&l)
+            .on_line(n)
+            .context(InstructionInvalidSnafu)
+    })
+}
+
+fn signal_strengths(instructions: impl Iterator<Item = Instruction>) -> i32 {
+    x_values(instructions)
+        .skip(20 - 1) // One based cycle indexing
+        .step_by(40)
+        .take(6)
+        .map(|(i, x)| i32::from(i) * x)
+        .sum()
+}
+
+fn render(instructions: impl Iterator<Item = Instruction>) -> String {
+    let mut rendered = String::new();
+    let mut crt_x_position = (0..i32::from(DISPLAY_WIDTH)).cycle();
+
+    let n_pixels = usize::from(DISPLAY_WIDTH) * usize::from(DISPLAY_HEIGHT);
+
+    for (_cycle, x_value) in x_values(instructions).take(n_pixels) {
+        let crt_x_position = crt_x_position.next().expect("Infinite iterator ended");
+        let pixel_range = x_value - 1..=x_value + 1;
+        let pixel = if pixel_range.contains(&crt_x_position) {
+            '#'
+        } else {
+            '.'
+        };
+        rendered.push(pixel);
+        if crt_x_position == 39 {
+            rendered.push('\n');
+        }
+    }
+
+    rendered
+}
+
+fn x_values(
+    mut instructions: impl Iterator<Item = Instruction>,
+) -> impl Iterator<Item = (u16, i32)> {
+    let mut cycle = 1u16;
+    let mut x = 1;
+    let mut cached_add_x = None;
+
+    iter::from_fn(move || {
+        let orig_cycle = cycle;
+        let orig_x = x;
+
+        if let Some((mut time, value)) = cached_add_x.take() {
+            time -= 1;
+
+            if time == 0 {
+                x += value;
+            } else {
+                cached_add_x = Some((time, value));
+            }
+        } else {
+            let instruction = instructions.next().unwrap_or_default();
+
+            match instruction {
+                Instruction::Noop => {}
+                Instruction::AddX(v) => {
+                    // Minus 1 because we started the operation and it's been running
+                    cached_add_x = Some((ADD_X_DURATION - 1, v));
+                }
+            }
+        }
+
+        cycle += 1;
+
+        Some((orig_cycle, orig_x))
+    })
+}
+
+/// One instruction of the CPU.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Instruction {
+    #[default]
+    Noop,
+    AddX(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = Located<InstructionError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use instruction_error::*;
+        use Instruction::*;
+
+        let mut parts = s.split_whitespace();
+        let instruction = parts
+            .next()
+            .context(InstructionMissingSnafu)
+            .locate(s, end_of(s))?;
+        Ok(match instruction {
+            "noop" => Noop,
+            "addx" => {
+                let value = parts
+                    .next()
+                    .context(AddXValueMissingSnafu)
+                    .locate(s, end_of(s))?;
+                let value = value
+                    .parse()
+                    .context(InvalidAddXValueSnafu)
+                    .locate(s, value)?;
+                AddX(value)
+            }
+            _ => {
+                return UnknownInstructionSnafu { instruction }
+                    .fail()
+                    .locate(s, instruction)
+            }
+        })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::AddX(v) => write!(f, "addx {v}"),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    InstructionInvalid { source: Located<InstructionError> },
+
+    Read { source: std::io::Error },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum InstructionError {
+    InstructionMissing,
+
+    UnknownInstruction { instruction: String },
+
+    AddXValueMissing,
+
+    InvalidAddXValue { source: std::num::ParseIntError },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    // const INPUT_TINY: &str = include_str!("../input-tiny.test");
+
+    // #[test]
+    // #[snafu::report]
+    // fn example_tiny() -> Result<()> {
+    //     assert_eq!(13140, sum_of_six_signal_strengths(INPUT_TINY)?);
+    //     Ok(())
+    // }
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Programs mixing real instructions with an `addx` missing its
+    /// value and a `jump` that doesn't exist.
+    const ARBITRARY_INPUT: &str = r"(noop\n|addx -?[0-9]{1,3}\n|addx\n|jump 1\n){0,20}";
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(13140, sum_of_six_signal_strengths(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######.....\n",
+            render_signal(INPUT)?,
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day10>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day10::parse(&s) {
+                let _ = Day10::part1(&parsed, Day10::PART1_PARAMS);
+                let _ = Day10::part2(&parsed, Day10::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_agrees_with_str(s in ARBITRARY_INPUT) {
+            // The reader stops once the display is drawn, so it never
+            // sees any problems in the instructions after that
+            if let Ok(from_str) = sum_of_six_signal_strengths(&s) {
+                prop_assert_eq!(from_str, sum_of_six_signal_strengths_from_reader(s.as_bytes())?);
+            }
+            if let Ok(from_str) = render_signal(&s) {
+                prop_assert_eq!(from_str, render_signal_from_reader(s.as_bytes())?);
+            }
+        }
+
+        #[test]
+        fn instruction_round_trips(
+            instruction in prop_oneof![Just(Instruction::Noop), any::<i32>().prop_map(Instruction::AddX)],
+        ) {
+            prop_assert_eq!(Some(instruction), instruction.to_string().parse().ok());
+        }
+    }
+}
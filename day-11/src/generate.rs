@@ -0,0 +1,132 @@
+use crate::{Day11, WorryLevel};
+use aoc_common::generate::{Generate, GenerateRng, Rng, SliceRandom};
+use std::fmt::Write;
+
+/// Each monkey tests a different prime. Their product has to stay
+/// small enough that squaring a worry level in part 2 can't overflow.
+const PRIMES: [WorryLevel; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+struct Spec {
+    items: Vec<WorryLevel>,
+    op: char,
+    rhs: Option<WorryLevel>,
+    test: WorryLevel,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl Spec {
+    fn apply(&self, old: WorryLevel) -> Option<WorryLevel> {
+        let rhs = self.rhs.unwrap_or(old);
+        match self.op {
+            '+' => old.checked_add(rhs),
+            _ => old.checked_mul(rhs),
+        }
+    }
+}
+
+/// `size` is the total number of items, spread across up to 8
+/// monkeys.
+impl Generate for Day11 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let n_monkeys = size.clamp(2, 8);
+        let n_items = size.max(n_monkeys);
+
+        let monkeys = loop {
+            let monkeys = monkeys(rng, n_monkeys, n_items);
+            if part1_fits(&monkeys) {
+                break monkeys;
+            }
+        };
+
+        let mut s = String::new();
+
+        for (id, m) in monkeys.iter().enumerate() {
+            if id != 0 {
+                s.push('\n');
+            }
+
+            let items: Vec<_> = m.items.iter().map(ToString::to_string).collect();
+            let rhs = m.rhs.map_or_else(|| "old".into(), |v| v.to_string());
+
+            writeln!(s, "Monkey {id}:").expect("Unable to write to a string");
+            writeln!(s, "  Starting items: {}", items.join(", "))
+                .expect("Unable to write to a string");
+            writeln!(s, "  Operation: new = old {} {rhs}", m.op)
+                .expect("Unable to write to a string");
+            writeln!(s, "  Test: divisible by {}", m.test).expect("Unable to write to a string");
+            writeln!(s, "    If true: throw to monkey {}", m.if_true)
+                .expect("Unable to write to a string");
+            writeln!(s, "    If false: throw to monkey {}", m.if_false)
+                .expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
+
+fn monkeys(rng: &mut GenerateRng, n_monkeys: usize, n_items: usize) -> Vec<Spec> {
+    let mut tests = PRIMES;
+    tests.shuffle(rng);
+
+    let mut monkeys: Vec<_> = tests[..n_monkeys]
+        .iter()
+        .enumerate()
+        .map(|(id, &test)| {
+            let (op, rhs) = match rng.gen_range(0..10) {
+                0 => ('*', None),
+                1..=4 => ('+', Some(rng.gen_range(1..=8))),
+                _ => ('*', Some(rng.gen_range(2..=19))),
+            };
+
+            let mut other = || (id + rng.gen_range(1..n_monkeys)) % n_monkeys;
+
+            Spec {
+                items: vec![],
+                op,
+                rhs,
+                test,
+                if_true: other(),
+                if_false: other(),
+            }
+        })
+        .collect();
+
+    for i in 0..n_items {
+        // Every monkey starts with at least one item
+        let id = if i < n_monkeys {
+            i
+        } else {
+            rng.gen_range(0..n_monkeys)
+        };
+        monkeys[id].items.push(rng.gen_range(50..=99));
+    }
+
+    monkeys
+}
+
+/// Part 1 doesn't keep the worry levels in check, so make sure that
+/// it can't overflow.
+fn part1_fits(monkeys: &[Spec]) -> bool {
+    let mut items: Vec<Vec<WorryLevel>> = monkeys.iter().map(|m| m.items.clone()).collect();
+
+    for _round in 0..20 {
+        for (id, m) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[id]) {
+                let Some(item) = m.apply(item) else {
+                    return false;
+                };
+                let item = item / 3;
+
+                let target = if item % m.test == 0 {
+                    m.if_true
+                } else {
+                    m.if_false
+                };
+                items[target].push(item);
+            }
+        }
+    }
+
+    true
+}
@@ -0,0 +1,540 @@
+//! Day 11: Monkey in the Middle
+//!
+//! The input describes monkeys that inspect items and throw them to
+//! one another based on a worry level. Part 1 plays 20 rounds, with
+//! worry calming after each inspection; part 2 plays 10,000 without.
+//! Both multiply the inspection counts of the two busiest monkeys.
+
+use aoc_common::{diagnostic::end_of, LocateExt, Located, Solution};
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt, mem,
+    str::FromStr,
+};
+
+mod generate;
+
+/// The parsed input is every [`Monkey`], in order.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Vec<Monkey>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        let monkeys = Monkey::parse_barrel(s)
+            .collect::<MonkeyParseResult<Vec<_>>>()
+            .context(MonkeyInvalidSnafu)?;
+
+        for (monkey, m) in monkeys.iter().enumerate() {
+            for ThrowTo(target) in [m.if_true, m.if_false] {
+                ensure!(target != monkey, ThrowsToItselfSnafu { monkey });
+                ensure!(
+                    target < monkeys.len(),
+                    ThrowsToUnknownMonkeySnafu { monkey, target }
+                );
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Input<'_>, (): ()) -> Result<usize> {
+        let monkeys = monkeys
+            .iter()
+            .cloned()
+            .map(|m| (RefCell::new(m), Cell::new(0)))
+            .collect::<Vec<_>>();
+
+        for _round in 0..20 {
+            one_round::<true>(&monkeys)?;
+        }
+
+        Ok(product_of_two_most_active_monkey_actions(&monkeys))
+    }
+
+    fn part2(monkeys: &Self::Input<'_>, (): ()) -> Result<usize> {
+        let monkeys = monkeys
+            .iter()
+            .cloned()
+            .map(|m| (RefCell::new(m), Cell::new(0)))
+            .collect::<Vec<_>>();
+
+        let max_relevant_worry_level = monkeys
+            .iter()
+            .try_fold(1, |acc: WorryLevel, (m, _)| {
+                acc.checked_mul(m.borrow().test.0)
+            })
+            .context(WorryLevelOverflowSnafu)?;
+
+        for _round in 0..10_000 {
+            one_round::<false>(&monkeys)?;
+            for (m, _) in &monkeys {
+                let mut m = m.borrow_mut();
+                for item in &mut m.starting_items {
+                    *item %= max_relevant_worry_level;
+                }
+            }
+        }
+
+        Ok(product_of_two_most_active_monkey_actions(&monkeys))
+    }
+}
+
+/// The level of monkey business after 20 calm rounds.
+pub fn monkey_business(s: &str) -> Result<usize> {
+    Day11::part1(&Day11::parse(s)?, ())
+}
+
+/// The level of monkey business after 10,000 rounds without calming.
+pub fn monkey_business_high_stakes(s: &str) -> Result<usize> {
+    Day11::part2(&Day11::parse(s)?, ())
+}
+
+fn one_round<const DECREASE_WORRY: bool>(monkeys: &[(RefCell<Monkey>, Cell<usize>)]) -> Result<()> {
+    for (monkey, items_inspected) in monkeys {
+        let mut monkey = monkey.borrow_mut();
+
+        let items = mem::take(&mut monkey.starting_items);
+        items_inspected.update(|v| v + items.len());
+
+        for item in items {
+            let mut worry_level = monkey
+                .apply_operation(item)
+                .context(WorryLevelOverflowSnafu)?;
+
+            if DECREASE_WORRY {
+                worry_level /= 3;
+            }
+
+            let target = if monkey.apply_test(worry_level) {
+                monkey.if_true
+            } else {
+                monkey.if_false
+            };
+
+            let mut target = monkeys[target.0].0.borrow_mut();
+            target.starting_items.push_back(worry_level);
+        }
+    }
+
+    Ok(())
+}
+
+fn product_of_two_most_active_monkey_actions(monkeys: &[(RefCell<Monkey>, Cell<usize>)]) -> usize {
+    monkeys
+        .iter()
+        .map(|(_, n_items)| n_items.get())
+        .sorted()
+        .rev()
+        .take(2)
+        .product()
+}
+
+type MonkeyId = usize;
+type WorryLevel = u64;
+
+/// A monkey's items and how it decides where to throw them.
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    starting_items: VecDeque<WorryLevel>,
+    operation: Operation,
+    test: DivisibleBy,
+    if_true: ThrowTo,
+    if_false: ThrowTo,
+}
+
+impl Monkey {
+    fn parse_barrel(s: &str) -> impl Iterator<Item = MonkeyParseResult<Self>> + '_ {
+        let mut lines = s.lines().fuse().peekable();
+
+        std::iter::from_fn(move || {
+            lines.peek()?;
+            let monkey = match Self::from_lines(s, &mut lines) {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e)),
+            };
+            lines.next(); // Skip the blank line
+            Some(Ok(monkey))
+        })
+    }
+
+    // Monkey 0:
+    //   Starting items: 79, 98
+    //   Operation: new = old * 19
+    //   Test: divisible by 23
+    //     If true: throw to monkey 2
+    //     If false: throw to monkey 3
+    fn from_lines<'a>(s: &str, mut i: impl Iterator<Item = &'a str>) -> MonkeyParseResult<Self> {
+        use monkey_parse_error::*;
+
+        let eof = end_of(s);
+
+        let _id = i.next().context(IdMissingSnafu).locate(s, eof)?;
+        let starting_items_line = i.next().context(StartingItemsMissingSnafu).locate(s, eof)?;
+        let operation_line = i.next().context(OperationMissingSnafu).locate(s, eof)?;
+        let test_line = i.next().context(TestMissingSnafu).locate(s, eof)?;
+        let if_true_line = i.next().context(IfTrueMissingSnafu).locate(s, eof)?;
+        let if_false_line = i.next().context(IfFalseMissingSnafu).locate(s, eof)?;
+
+        let starting_items = starting_items_line
+            .trim()
+            .strip_prefix("Starting items: ")
+            .context(StartingItemsMalformedSnafu)
+            .locate(s, starting_items_line)?;
+        let starting_items = starting_items
+            .split(',')
+            .map(|item| {
+                let item = item.trim();
+                item.parse()
+                    .context(StartingItemInvalidSnafu { item })
+                    .locate(s, item)
+            })
+            .collect::<MonkeyParseResult<_>>()?;
+
+        let operation = operation_line
+            .trim()
+            .strip_prefix("Operation: ")
+            .context(OperationMalformedSnafu)
+            .locate(s, operation_line)?;
+        let operation = operation
+            .parse()
+            .context(OperationInvalidSnafu)
+            .locate(s, operation)?;
+
+        let test = test_line
+            .trim()
+            .strip_prefix("Test: ")
+            .context(TestMalformedSnafu)
+            .locate(s, test_line)?;
+        let test = test.parse().context(TestInvalidSnafu).locate(s, test)?;
+
+        let if_true = if_true_line
+            .trim()
+            .strip_prefix("If true: ")
+            .context(IfTrueMalformedSnafu)
+            .locate(s, if_true_line)?;
+        let if_true = if_true
+            .parse()
+            .context(IfTrueInvalidSnafu)
+            .locate(s, if_true)?;
+
+        let if_false = if_false_line
+            .trim()
+            .strip_prefix("If false: ")
+            .context(IfFalseMalformedSnafu)
+            .locate(s, if_false_line)?;
+        let if_false = if_false
+            .parse()
+            .context(IfFalseInvalidSnafu)
+            .locate(s, if_false)?;
+
+        Ok(Self {
+            starting_items,
+            operation,
+            test,
+            if_true,
+            if_false,
+        })
+    }
+
+    fn apply_operation(&self, item: WorryLevel) -> Option<WorryLevel> {
+        use {Op::*, Rhs::*};
+
+        let rhs = match self.operation.rhs {
+            Literal(v) => v,
+            Old => item,
+        };
+
+        match self.operation.op {
+            Add => item.checked_add(rhs),
+            Multiply => item.checked_mul(rhs),
+        }
+    }
+
+    fn apply_test(&self, item: WorryLevel) -> bool {
+        item.is_multiple_of(self.test.0)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum MonkeyParseError {
+    IdMissing,
+    StartingItemsMissing,
+    OperationMissing,
+    TestMissing,
+    IfTrueMissing,
+    IfFalseMissing,
+
+    StartingItemsMalformed,
+    OperationMalformed,
+    TestMalformed,
+    IfTrueMalformed,
+    IfFalseMalformed,
+
+    StartingItemInvalid {
+        source: std::num::ParseIntError,
+        item: String,
+    },
+    OperationInvalid {
+        source: OperationParseError,
+    },
+    TestInvalid {
+        source: DivisibleByParseError,
+    },
+    IfTrueInvalid {
+        source: ThrowToParseError,
+    },
+    IfFalseInvalid {
+        source: ThrowToParseError,
+    },
+}
+
+type MonkeyParseResult<T, E = Located<MonkeyParseError>> = std::result::Result<T, E>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Operation {
+    op: Op,
+    rhs: Rhs,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Rhs {
+    Literal(WorryLevel),
+    Old,
+}
+
+impl FromStr for Operation {
+    type Err = OperationParseError;
+
+    // new = old * 19
+    fn from_str(s: &str) -> OperationParseResult<Self> {
+        use operation_parse_error::*;
+
+        let (_, operation) = s.split_once('=').context(MalformedSnafu)?;
+
+        let mut operation = operation.split_whitespace();
+        let lhs = operation.next().context(LeftHandSideMissingSnafu)?;
+        let op = operation.next().context(OperationMissingSnafu)?;
+        let rhs = operation.next().context(RightHandSideMissingSnafu)?;
+
+        ensure!(lhs == "old", LeftHandSideUnknownSnafu);
+        let op = match op {
+            "+" => Op::Add,
+            "*" => Op::Multiply,
+            _ => return OpUnknownSnafu { op }.fail(),
+        };
+        let rhs = match rhs {
+            "old" => Rhs::Old,
+            rhs => rhs
+                .parse()
+                .ok()
+                .map(Rhs::Literal)
+                .context(RightHandSideUnknownSnafu { rhs })?,
+        };
+
+        Ok(Self { op, rhs })
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Op::Add => "+",
+            Op::Multiply => "*",
+        };
+        match self.rhs {
+            Rhs::Literal(v) => write!(f, "new = old {op} {v}"),
+            Rhs::Old => write!(f, "new = old {op} old"),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum OperationParseError {
+    Malformed,
+
+    LeftHandSideMissing,
+    OperationMissing,
+    RightHandSideMissing,
+
+    LeftHandSideUnknown,
+    OpUnknown { op: String },
+    RightHandSideUnknown { rhs: String },
+}
+type OperationParseResult<T, E = OperationParseError> = std::result::Result<T, E>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DivisibleBy(WorryLevel);
+
+impl FromStr for DivisibleBy {
+    type Err = DivisibleByParseError;
+
+    // divisible by 23
+    fn from_str(s: &str) -> DivisibleByParseResult<Self> {
+        use divisible_by_parse_error::*;
+
+        let v = s
+            .trim()
+            .strip_prefix("divisible by ")
+            .context(MalformedSnafu)?;
+        let v = v.parse().context(ValueInvalidSnafu { v })?;
+        ensure!(v != 0, ZeroSnafu);
+
+        Ok(Self(v))
+    }
+}
+
+impl fmt::Display for DivisibleBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "divisible by {}", self.0)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum DivisibleByParseError {
+    Malformed,
+
+    Zero,
+
+    ValueInvalid {
+        source: std::num::ParseIntError,
+        v: String,
+    },
+}
+
+type DivisibleByParseResult<T, E = DivisibleByParseError> = std::result::Result<T, E>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct ThrowTo(MonkeyId);
+
+impl FromStr for ThrowTo {
+    type Err = ThrowToParseError;
+
+    // throw to monkey 2
+    fn from_str(s: &str) -> ThrowToParseResult<Self> {
+        use throw_to_parse_error::*;
+
+        let v = s
+            .trim()
+            .strip_prefix("throw to monkey ")
+            .context(MalformedSnafu)?;
+        let v = v.parse().context(ValueInvalidSnafu { v })?;
+
+        Ok(Self(v))
+    }
+}
+
+impl fmt::Display for ThrowTo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "throw to monkey {}", self.0)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ThrowToParseError {
+    Malformed,
+
+    ValueInvalid {
+        source: std::num::ParseIntError,
+        v: String,
+    },
+}
+
+type ThrowToParseResult<T, E = ThrowToParseError> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    MonkeyInvalid { source: Located<MonkeyParseError> },
+
+    ThrowsToItself { monkey: MonkeyId },
+
+    ThrowsToUnknownMonkey { monkey: MonkeyId, target: MonkeyId },
+
+    WorryLevelOverflow,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(10605, monkey_business(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(2713310158, monkey_business_high_stakes(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day11>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"(Monkey [0-9]:\n  Starting items: [0-9]{1,2}(, [0-9]{1,2}){0,3}\n  Operation: new = old [*+-] (old|[0-9])\n  Test: divisible by [0-9]\n    If true: throw to monkey [0-9]\n    If false: throw to monkey [0-9]\n\n){1,4}") {
+            if let Ok(parsed) = Day11::parse(&s) {
+                let _ = Day11::part1(&parsed, Day11::PART1_PARAMS);
+                let _ = Day11::part2(&parsed, Day11::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn operation_round_trips(
+            op in prop::sample::select(&[Op::Add, Op::Multiply][..]),
+            rhs in prop_oneof![Just(Rhs::Old), any::<WorryLevel>().prop_map(Rhs::Literal)],
+        ) {
+            let operation = Operation { op, rhs };
+            prop_assert_eq!(Some(operation), operation.to_string().parse().ok());
+        }
+
+        #[test]
+        fn divisible_by_round_trips(v in 1..=WorryLevel::MAX) {
+            let test = DivisibleBy(v);
+            prop_assert_eq!(Some(test), test.to_string().parse().ok());
+        }
+
+        #[test]
+        fn throw_to_round_trips(v: MonkeyId) {
+            let throw_to = ThrowTo(v);
+            prop_assert_eq!(Some(throw_to), throw_to.to_string().parse().ok());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Day12;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+
+/// The number of steps from the lowest to the highest elevation.
+const CLIMB: usize = 25;
+
+/// `size` is the number of rows; the map is wider than it is tall.
+impl Generate for Day12 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let height = size.max(1);
+        let width = CLIMB + 1 + size;
+
+        let start = (0, rng.gen_range(0..height));
+        let end = (width - 1, rng.gen_range(0..height));
+        let distance =
+            |(ax, ay): (usize, usize), (bx, by): (usize, usize)| ax.abs_diff(bx) + ay.abs_diff(by);
+
+        // Each peak's elevation falls off by one per step, so
+        // neighbouring squares never differ by more than one and every
+        // square can be reached from every other. The end is the
+        // highest peak and the start is too far away from any peak to
+        // be anything but the lowest elevation.
+        let mut peaks = vec![(end, 0)];
+        while peaks.len() < 1 + height / 2 {
+            let peak = (rng.gen_range(0..width), rng.gen_range(0..height));
+            let offset = rng.gen_range(1..=15);
+
+            if distance(peak, start) + offset >= CLIMB {
+                peaks.push((peak, offset));
+            }
+        }
+
+        let elevation = |x: usize, y: usize| {
+            let depth = peaks
+                .iter()
+                .map(|&(peak, offset)| distance(peak, (x, y)) + offset)
+                .min()
+                .unwrap_or(CLIMB);
+            CLIMB.saturating_sub(depth)
+        };
+
+        let mut s = String::with_capacity((width + 1) * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let c = if (x, y) == start {
+                    'S'
+                } else if (x, y) == end {
+                    'E'
+                } else {
+                    char::from(b'a' + elevation(x, y) as u8)
+                };
+                s.push(c);
+            }
+            s.push('\n');
+        }
+
+        s
+    }
+}
@@ -0,0 +1,361 @@
+//! Day 12: Hill Climbing Algorithm
+//!
+//! The input is a height map from `a` to `z`, with a start `S` and an
+//! end `E`; each step may climb at most one level. Part 1 finds the
+//! fewest steps from the start to the end; part 2 from any square of
+//! height `a`.
+
+use aoc_common::{
+    visualize::frame_from_grid, Alternative, Frame, Grid, Located, Palette, ParseGridError, Point,
+    Solution, Style,
+};
+use snafu::prelude::*;
+use std::collections::{BTreeSet, BinaryHeap, VecDeque};
+
+mod generate;
+
+/// The parsed input is the [`HeightMap`] with its start and end.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input<'a> = (HeightMap, Coord, Coord);
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_height_map(s)
+    }
+
+    fn part1((height_map, start, end): &Self::Input<'_>, (): ()) -> Result<usize> {
+        let path = find_path(height_map, *start, *end).context(NoPathFoundSnafu)?;
+
+        Ok(path.steps())
+    }
+
+    fn part2((height_map, _start, end): &Self::Input<'_>, (): ()) -> Result<usize> {
+        let starts = height_map
+            .iter()
+            .flat_map(|(coord, &height)| (height == 0).then_some(coord));
+
+        starts
+            .flat_map(|start| find_path(height_map, start, *end))
+            .map(|p| p.steps())
+            .min()
+            .context(NoMinimalPathFoundSnafu)
+    }
+
+    fn part1_alternatives() -> Vec<Alternative<Self, (), usize>> {
+        vec![("reverse-bfs", part1_reverse_bfs)]
+    }
+
+    fn part2_alternatives() -> Vec<Alternative<Self, (), usize>> {
+        vec![("reverse-bfs", part2_reverse_bfs)]
+    }
+}
+
+fn part1_reverse_bfs(
+    (height_map, start, end): &(HeightMap, Coord, Coord),
+    (): (),
+) -> Result<usize> {
+    steps_back_from(height_map, *end, |c, _| c == *start).context(NoPathFoundSnafu)
+}
+
+/// A single search from the end finds the closest lowest square,
+/// instead of searching from every one of them.
+fn part2_reverse_bfs(
+    (height_map, _start, end): &(HeightMap, Coord, Coord),
+    (): (),
+) -> Result<usize> {
+    steps_back_from(height_map, *end, |_, height| height == 0).context(NoMinimalPathFoundSnafu)
+}
+
+/// The fewest steps from the start to the end.
+pub fn fewest_steps_to_goal(s: &str) -> Result<usize> {
+    Day12::part1(&Day12::parse(s)?, ())
+}
+
+/// As [`fewest_steps_to_goal`], calling `f` with the height map and
+/// each square of the path taken.
+pub fn fewest_steps_to_goal_with(s: &str, f: impl FnOnce(&HeightMap, &[Coord])) -> Result<usize> {
+    let (height_map, start, end) = Day12::parse(s)?;
+    let path = find_path(&height_map, start, end).context(NoPathFoundSnafu)?;
+    f(&height_map, &path.0);
+    Ok(path.steps())
+}
+
+/// The fewest steps from any lowest square to the end.
+pub fn fewest_steps_from_scenic_start_to_goal(s: &str) -> Result<usize> {
+    Day12::part2(&Day12::parse(s)?, ())
+}
+
+pub type Coord = Point<usize>;
+/// The height of each square, from 0 for `a` to 25 for `z`.
+pub type HeightMap = Grid<u8>;
+
+/// What is drawn at each square.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Square {
+    Height(u8),
+    Start,
+    Path,
+    End,
+}
+
+/// The hill shades from dark to light as it rises, with the path in
+/// red.
+pub fn palette() -> Palette<Square> {
+    let palette = Palette::new(Style::new(' ', [0, 0, 0]))
+        .with(Square::Start, Style::new('S', [255, 255, 0]))
+        .with(Square::Path, Style::new('#', [255, 0, 0]))
+        .with(Square::End, Style::new('E', [255, 255, 0]));
+
+    (0..26).fold(palette, |palette, height| {
+        let shade = 40 + height * 8;
+        let style = Style::new(h(height), [shade / 2, shade, shade / 2]);
+        palette.with(Square::Height(height), style)
+    })
+}
+
+/// Every square of the height map, with `path` drawn on top.
+pub fn path_frame(height_map: &HeightMap, path: &[Coord]) -> Frame<Square> {
+    let start = path.first();
+    let end = path.last();
+
+    frame_from_grid(height_map, |p, &height| {
+        Some(if start == Some(&p) {
+            Square::Start
+        } else if end == Some(&p) {
+            Square::End
+        } else if path.contains(&p) {
+            Square::Path
+        } else {
+            Square::Height(height)
+        })
+    })
+}
+
+fn parse_height_map(s: &str) -> Result<(HeightMap, Coord, Coord)> {
+    let mut start = None;
+    let mut end = None;
+
+    let height_map = Grid::parse(s, |coord, c| {
+        let c = match c {
+            'S' => {
+                start = Some(coord);
+                'a'
+            }
+            'E' => {
+                end = Some(coord);
+                'z'
+            }
+            'a'..='z' => c,
+            c => return InvalidDigitSnafu { c }.fail(),
+        };
+        let height = c as u8 - b'a';
+
+        Ok(height)
+    })
+    .context(HeightMapInvalidSnafu)?;
+
+    let start = start.context(StartMissingSnafu)?;
+    let end = end.context(EndMissingSnafu)?;
+
+    Ok((height_map, start, end))
+}
+
+fn h(b: u8) -> char {
+    (b + b'a') as char
+}
+
+fn find_path(height_map: &HeightMap, start: Coord, end: Coord) -> Option<Path> {
+    let mut visited = BTreeSet::new();
+    let mut paths = BinaryHeap::from([Path::new(start)]);
+
+    while let Some(path) = paths.pop() {
+        let current_pos = path.end();
+        let current_height = height_map[current_pos];
+
+        let newly_visited = visited.insert(current_pos);
+        if !newly_visited {
+            continue;
+        }
+
+        for candidate in height_map.neighbors(current_pos) {
+            if visited.contains(&candidate) {
+                continue;
+            }
+
+            if height_map[candidate] <= (current_height + 1) {
+                let next_path = path.append(candidate);
+
+                if candidate == end {
+                    return Some(next_path);
+                } else {
+                    paths.push(next_path);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Searches outward from `end`, taking each step backwards, until
+/// reaching a square that `is_goal` accepts. Squares are visited in
+/// order of distance, so the first goal reached is the closest.
+fn steps_back_from(
+    height_map: &HeightMap,
+    end: Coord,
+    is_goal: impl Fn(Coord, u8) -> bool,
+) -> Option<usize> {
+    let mut visited = BTreeSet::from([end]);
+    let mut queue = VecDeque::from([(end, 0)]);
+
+    while let Some((current_pos, steps)) = queue.pop_front() {
+        let current_height = height_map[current_pos];
+
+        if is_goal(current_pos, current_height) {
+            return Some(steps);
+        }
+
+        for candidate in height_map.neighbors(current_pos) {
+            // Stepping forward from the candidate climbs at most one level
+            if current_height <= height_map[candidate] + 1 && visited.insert(candidate) {
+                queue.push_back((candidate, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Path(Vec<Coord>);
+
+impl PartialOrd for Path {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Path {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.len().cmp(&other.0.len()).reverse()
+    }
+}
+
+impl Path {
+    fn new(start: Coord) -> Self {
+        Self(vec![start])
+    }
+
+    fn steps(&self) -> usize {
+        self.0.len() - 1 // We track each location, not the steps between
+    }
+
+    fn end(&self) -> Coord {
+        self.0.last().copied().expect("Path was empty")
+    }
+
+    fn append(&self, end: Coord) -> Self {
+        let mut path = self.0.clone();
+        path.push(end);
+        Self(path)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("{c:?} is not a valid character"))]
+pub struct InvalidDigitError {
+    c: char,
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The height map is invalid"))]
+    HeightMapInvalid {
+        source: Located<ParseGridError<InvalidDigitError>>,
+    },
+
+    #[snafu(display("There was no start point"))]
+    StartMissing,
+
+    #[snafu(display("There was no end point"))]
+    EndMissing,
+
+    #[snafu(display("No path was found between the start and end points"))]
+    NoPathFound,
+
+    #[snafu(display("No path was found between any start point and the end point"))]
+    NoMinimalPathFound,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(31, fewest_steps_to_goal(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(29, fewest_steps_from_scenic_start_to_goal(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day12>(5, 20)
+    }
+
+    #[test]
+    #[snafu::report]
+    fn alternatives_agree() -> Result<()> {
+        let inputs = (0..5).map(|seed| aoc_common::generate::generate::<Day12>(seed, 20));
+
+        for input in std::iter::once(INPUT.to_owned()).chain(inputs) {
+            let parsed = Day12::parse(&input)?;
+            let part1 = Day12::part1(&parsed, ())?;
+            let part2 = Day12::part2(&parsed, ())?;
+
+            for (name, f) in Day12::part1_alternatives() {
+                assert_eq!(part1, f(&parsed, ())?, "{name}");
+            }
+            for (name, f) in Day12::part2_alternatives() {
+                assert_eq!(part2, f(&parsed, ())?, "{name}");
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"[SEa-e]{0,6}(\n[SEa-e]{0,6}){0,5}") {
+            if let Ok(parsed) = Day12::parse(&s) {
+                let _ = Day12::part1(&parsed, Day12::PART1_PARAMS);
+                let _ = Day12::part2(&parsed, Day12::PART2_PARAMS);
+            }
+        }
+    }
+}
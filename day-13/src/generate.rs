@@ -0,0 +1,40 @@
+use crate::Day13;
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+
+/// `size` is the number of pairs of packets.
+impl Generate for Day13 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let mut s = String::new();
+
+        for i in 0..size {
+            if i != 0 {
+                s.push('\n');
+            }
+
+            for _ in 0..2 {
+                list(rng, 0, &mut s);
+                s.push('\n');
+            }
+        }
+
+        s
+    }
+}
+
+fn list(rng: &mut GenerateRng, depth: usize, s: &mut String) {
+    s.push('[');
+
+    for i in 0..rng.gen_range(0..=5) {
+        if i != 0 {
+            s.push(',');
+        }
+
+        if depth < 4 && rng.gen_bool(0.3) {
+            list(rng, depth + 1, s);
+        } else {
+            s.push_str(&rng.gen_range(0..=10).to_string());
+        }
+    }
+
+    s.push(']');
+}
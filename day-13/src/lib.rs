@@ -0,0 +1,397 @@
+//! Day 13: Distress Signal
+//!
+//! The input is pairs of packets, nested lists of integers. Part 1
+//! sums the indices of the pairs already in the right order; part 2
+//! sorts every packet along with two divider packets and multiplies
+//! the dividers' positions.
+
+use aoc_common::{diagnostic::end_of, LocateExt, Located, Solution, WithinExt};
+use snafu::prelude::*;
+use std::{cmp::Ordering, fmt, slice, str::FromStr};
+
+mod generate;
+
+/// The parsed input is every [`Pair`] of packets.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Vec<Pair>;
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        Pair::parse_many(s)
+            .collect::<PairParseResult<_>>()
+            .context(InvalidPairSnafu)
+    }
+
+    fn part1(pairs: &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, pair)| pair.is_in_right_order())
+            .map(|(i, _)| i + 1) // One-based indexing
+            .sum())
+    }
+
+    fn part2(pairs: &Self::Input<'_>, (): ()) -> Result<usize> {
+        let mut packets = pairs
+            .iter()
+            .flat_map(|Pair(l, r)| [l, r])
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let key_a = Packet::make_key(2);
+        let key_b = Packet::make_key(6);
+
+        packets.push(key_a.clone());
+        packets.push(key_b.clone());
+
+        packets.sort();
+
+        let a = packets
+            .iter()
+            .position(|p| p == &key_a)
+            .expect("Manually inserted key 'a' went missing");
+        let b = packets
+            .iter()
+            .position(|p| p == &key_b)
+            .expect("Manually inserted key 'b' went missing");
+
+        // One-based indexing
+        let a = a + 1;
+        let b = b + 1;
+
+        Ok(a * b)
+    }
+}
+
+/// The summed one-based indices of the pairs in the right order.
+pub fn sum_of_indices_of_pairs_in_right_order(s: &str) -> Result<usize> {
+    Day13::part1(&Day13::parse(s)?, ())
+}
+
+/// The product of the divider packets' positions once sorted.
+pub fn decoder_key(s: &str) -> Result<usize> {
+    Day13::part2(&Day13::parse(s)?, ())
+}
+
+/// Two packets that should be in the right order.
+#[derive(Debug)]
+pub struct Pair(Packet, Packet);
+
+impl Pair {
+    fn parse_many(s: &str) -> impl Iterator<Item = PairParseResult<Self>> + '_ {
+        let mut lines = s.lines().fuse().peekable();
+        std::iter::from_fn(move || {
+            lines.peek()?;
+            Some(Self::parse_one(s, &mut lines))
+        })
+    }
+
+    fn parse_one<'a>(s: &str, mut i: impl Iterator<Item = &'a str>) -> PairParseResult<Self> {
+        use pair_parse_error::*;
+
+        let left = i.next().context(LeftMissingSnafu).locate(s, end_of(s))?;
+        let right = i.next().context(RightMissingSnafu).locate(s, end_of(s))?;
+        i.next(); // Burn the empty line
+
+        let left = left
+            .parse()
+            .within(s, left)
+            .map_err(|e| e.map(|source| PairParseError::LeftInvalid { source }))?;
+        let right = right
+            .parse()
+            .within(s, right)
+            .map_err(|e| e.map(|source| PairParseError::RightInvalid { source }))?;
+
+        Ok(Self(left, right))
+    }
+
+    pub fn left(&self) -> &Packet {
+        &self.0
+    }
+
+    pub fn right(&self) -> &Packet {
+        &self.1
+    }
+
+    /// The left packet must sort strictly before the right.
+    pub fn is_in_right_order(&self) -> bool {
+        self.0.cmp(&self.1) == Ordering::Less
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum PairParseError {
+    LeftMissing,
+    RightMissing,
+    LeftInvalid { source: PacketParseError },
+    RightInvalid { source: PacketParseError },
+}
+
+type PairParseResult<T, E = Located<PairParseError>> = std::result::Result<T, E>;
+
+/// Guards the recursive parser against overflowing the stack.
+const MAX_DEPTH: usize = 100;
+
+/// An integer or a list of packets.
+///
+/// Packets are ordered as the puzzle describes: integers by value and
+/// lists element by element, with an integer compared against a list
+/// as if it were a list of one.
+///
+/// ```
+/// use day_13::Packet;
+///
+/// let left: Packet = "[[1],[2,3,4]]".parse()?;
+/// let right: Packet = "[[1],4]".parse()?;
+/// assert!(left < right);
+/// assert_eq!("[[1],4]", right.to_string());
+/// # Ok::<_, aoc_common::Located<day_13::PacketParseError>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Value(u32),
+    List(Vec<Self>),
+}
+
+impl FromStr for Packet {
+    type Err = Located<PacketParseError>;
+
+    fn from_str(s: &str) -> PacketParseResult<Self> {
+        use packet_parse_error::*;
+
+        let input = s;
+
+        fn walking_parse<'a>(
+            input: &str,
+            s: &'a str,
+            depth: usize,
+        ) -> PacketParseResult<(Packet, &'a str)> {
+            use packet_parse_error::*;
+            use Packet::*;
+
+            match s.strip_prefix('[') {
+                Some(mut s) => {
+                    if depth >= MAX_DEPTH {
+                        return TooDeepSnafu.fail().locate(input, &s[..0]);
+                    }
+
+                    let mut children = vec![];
+                    loop {
+                        if s.is_empty() || s.starts_with(']') {
+                            break;
+                        }
+
+                        let (child, tail) = walking_parse(input, s, depth + 1)?;
+                        children.push(child);
+
+                        if let Some(tail) = tail.strip_prefix(',') {
+                            s = tail;
+                        } else {
+                            s = tail;
+                            break;
+                        }
+                    }
+                    // Point at whatever is where the bracket should be
+                    let s = s
+                        .strip_prefix(']')
+                        .context(ListUnclosedSnafu)
+                        .locate(input, &s[..0])?;
+                    Ok((List(children), s))
+                }
+                None => {
+                    let idx = s
+                        .char_indices()
+                        .find_map(|(i, c)| if c.is_ascii_digit() { None } else { Some(i) })
+                        .unwrap_or(s.len());
+                    let (head, tail) = s.split_at(idx);
+                    let value = head
+                        .parse()
+                        .context(ValueInvalidSnafu { s: head })
+                        .locate(input, head)?;
+                    Ok((Value(value), tail))
+                }
+            }
+        }
+
+        let (this, tail) = walking_parse(input, s, 0)?;
+        if !tail.is_empty() {
+            return TrailingDataSnafu { tail }.fail().locate(input, tail);
+        }
+        Ok(this)
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Value(v) => write!(f, "{v}"),
+            Packet::List(children) => {
+                write!(f, "[")?;
+                for (i, child) in children.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{child}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Packet {
+    fn make_key(v: u32) -> Self {
+        use Packet::*;
+
+        List(vec![List(vec![Value(v)])])
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Less == right order
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use Packet::*;
+
+        let left = self;
+        let right = other;
+
+        fn list_order(left: &[Packet], right: &[Packet]) -> Ordering {
+            let mut left = left.iter();
+            let mut right = right.iter();
+
+            loop {
+                match (left.next(), right.next()) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(l), Some(r)) => match l.cmp(r) {
+                        Ordering::Equal => continue,
+                        other => return other,
+                    },
+                };
+            }
+        }
+
+        match (left, right) {
+            (Value(l), Value(r)) => l.cmp(r),
+            (List(l), List(r)) => list_order(l, r),
+            (l, List(r)) => {
+                let l = slice::from_ref(l);
+                list_order(l, r)
+            }
+            (List(l), r) => {
+                let r = slice::from_ref(r);
+                list_order(l, r)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum PacketParseError {
+    ValueInvalid {
+        source: std::num::ParseIntError,
+        s: String,
+    },
+
+    ListUnclosed,
+
+    #[snafu(display("Lists may only be nested {MAX_DEPTH} deep"))]
+    TooDeep,
+
+    TrailingData {
+        tail: String,
+    },
+}
+
+type PacketParseResult<T, E = Located<PacketParseError>> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    InvalidPair { source: Located<PairParseError> },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let leaf = any::<u32>().prop_map(Packet::Value);
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop::collection::vec(inner, 0..8).prop_map(Packet::List)
+        })
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(13, sum_of_indices_of_pairs_in_right_order(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(140, decoder_key(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    fn deeply_nested_packets_are_rejected() {
+        let input = "[".repeat(100_000);
+
+        let Err(e) = input.parse::<Packet>() else {
+            panic!("The packet should have been rejected");
+        };
+
+        assert!(matches!(e.source, PacketParseError::TooDeep));
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day13>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in r"(\[[0-9\[\],]{0,12}\n\[[0-9\[\],]{0,12}\n\n){0,4}") {
+            if let Ok(parsed) = Day13::parse(&s) {
+                let _ = Day13::part1(&parsed, Day13::PART1_PARAMS);
+                let _ = Day13::part2(&parsed, Day13::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn packet_round_trips(packet in packet()) {
+            let parsed = packet.to_string().parse().ok();
+            prop_assert_eq!(Some(packet), parsed);
+        }
+    }
+}
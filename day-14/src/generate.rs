@@ -0,0 +1,49 @@
+use crate::{Day14, Dim, ORIGIN_POINT};
+use aoc_common::generate::{Generate, GenerateRng, Rng};
+use std::fmt::Write;
+
+/// `size` is the number of rock paths, of which there is at least one.
+/// The cave gets deeper as more paths are added.
+impl Generate for Day14 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let depth = 10 + Dim::try_from(size).unwrap_or(Dim::MAX).min(480);
+
+        // Stay below the source of the sand and away from the left
+        // edge of the coordinate space
+        let xs = ORIGIN_POINT.x - depth..=ORIGIN_POINT.x + depth;
+        let ys = ORIGIN_POINT.y + 2..=ORIGIN_POINT.y + depth;
+
+        let mut s = String::new();
+
+        for _ in 0..size.max(1) {
+            let mut x = rng.gen_range(xs.clone());
+            let mut y = rng.gen_range(ys.clone());
+            let mut horizontal = rng.gen();
+
+            let mut points = vec![format!("{x},{y}")];
+
+            for _ in 0..rng.gen_range(1..=4) {
+                let length = rng.gen_range(1..=8);
+                let (v, range) = if horizontal {
+                    (&mut x, &xs)
+                } else {
+                    (&mut y, &ys)
+                };
+
+                *v = if rng.gen() {
+                    *v + length
+                } else {
+                    v.saturating_sub(length)
+                };
+                *v = (*v).clamp(*range.start(), *range.end());
+                horizontal = !horizontal;
+
+                points.push(format!("{x},{y}"));
+            }
+
+            writeln!(s, "{}", points.join(" -> ")).expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
@@ -0,0 +1,337 @@
+//! Day 14: Regolith Reservoir
+//!
+//! The input traces the rock walls of a cave. Sand pours in from
+//! `(500, 0)`, one unit at a time. Part 1 counts the units that come
+//! to rest before sand falls into the abyss; part 2 adds a floor and
+//! counts the units until the source is blocked.
+
+use aoc_common::{
+    input::numbered_lines, BoundingBox, Frame, LocateExt, Located, Palette, Point, Solution,
+    SparseGrid, Style, WithinExt,
+};
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{io::BufRead, ops::ControlFlow};
+
+mod generate;
+
+/// The parsed input is the [`Map`] of walls and its lowest wall.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input<'a> = (Map, Dim);
+    type Error = Error;
+
+    type Part1Params = ();
+    type Part1 = usize;
+
+    type Part2Params = ();
+    type Part2 = usize;
+
+    const PART1_PARAMS: Self::Part1Params = ();
+    const PART2_PARAMS: Self::Part2Params = ();
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_map_and_max_y(s)
+    }
+
+    fn part1((map, max_y): &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(fill_until_sand_falls(map.clone(), *max_y, |_| {}))
+    }
+
+    fn part2((map, max_y): &Self::Input<'_>, (): ()) -> Result<usize> {
+        Ok(fill_until_source_blocked(map.clone(), *max_y, |_| {}))
+    }
+}
+
+/// How many units of sand come to rest before the rest fall forever.
+pub fn units_of_sand_come_to_rest(s: &str) -> Result<usize> {
+    Day14::part1(&Day14::parse(s)?, ())
+}
+
+/// How many units of sand come to rest on the floor before the source is blocked.
+pub fn units_of_sand_come_to_rest_infinite_floor(s: &str) -> Result<usize> {
+    Day14::part2(&Day14::parse(s)?, ())
+}
+
+/// As [`units_of_sand_come_to_rest`], calling `f` with the cave after
+/// each unit of sand comes to rest.
+pub fn units_of_sand_come_to_rest_with(s: &str, f: impl FnMut(&Map)) -> Result<usize> {
+    let (map, max_y) = Day14::parse(s)?;
+    Ok(fill_until_sand_falls(map, max_y, f))
+}
+
+/// As [`units_of_sand_come_to_rest_infinite_floor`], calling `f` with
+/// the cave after each unit of sand comes to rest.
+pub fn units_of_sand_come_to_rest_infinite_floor_with(
+    s: &str,
+    f: impl FnMut(&Map),
+) -> Result<usize> {
+    let (map, max_y) = Day14::parse(s)?;
+    Ok(fill_until_source_blocked(map, max_y, f))
+}
+
+/// As [`units_of_sand_come_to_rest`], reading one line at a time.
+pub fn units_of_sand_come_to_rest_from_reader(reader: impl BufRead) -> Result<usize> {
+    Day14::part1(&parse_map_and_max_y_from_reader(reader)?, ())
+}
+
+/// As [`units_of_sand_come_to_rest_infinite_floor`], reading one line
+/// at a time.
+pub fn units_of_sand_come_to_rest_infinite_floor_from_reader(
+    reader: impl BufRead,
+) -> Result<usize> {
+    Day14::part2(&parse_map_and_max_y_from_reader(reader)?, ())
+}
+
+const ORIGIN_POINT: Coord = Point::new(500, 0);
+
+/// Part 2 fills the whole triangle below the source with sand: about
+/// `depth²` grains, each kept in the map and each falling up to
+/// `depth` steps. At this depth that is already 100 million grains,
+/// over a gigabyte of map, and about a trillion steps, while the
+/// puzzle's inputs are under 200 deep. Limiting `x` as well means a
+/// single wall can't cost more to draw than that.
+const MAX_COORDINATE: Dim = 10_000;
+
+pub type Dim = u32;
+pub type Coord = Point<Dim>;
+/// What fills each point of the cave; empty points are absent.
+pub type Map = SparseGrid<State, Dim>;
+
+/// What fills a point of the cave.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    Wall,
+    Sand,
+}
+
+/// Sand on rock, as in the puzzle's own drawings.
+pub fn palette() -> Palette<State> {
+    Palette::new(Style::new('.', [24, 16, 8]))
+        .with(State::Wall, Style::new('#', [128, 128, 128]))
+        .with(State::Sand, Style::new('o', [240, 200, 96]))
+}
+
+/// The walls and sand of the cave, for drawing.
+pub fn cave_frame(map: &Map) -> Frame<State> {
+    map.iter()
+        .map(|(p, &state)| (Point::new(p.x.into(), p.y.into()), state))
+        .collect()
+}
+
+fn fill_until_sand_falls(mut map: Map, max_y: Dim, mut f: impl FnMut(&Map)) -> usize {
+    while let ControlFlow::Continue(()) = drop_one_sand(&mut map, |_| false, |sand| sand.y > max_y)
+    {
+        f(&map);
+    }
+
+    n_sand(&map)
+}
+
+fn fill_until_source_blocked(mut map: Map, max_y: Dim, mut f: impl FnMut(&Map)) -> usize {
+    while let ControlFlow::Continue(()) = drop_one_sand(
+        &mut map,
+        |coord| coord.y >= max_y + 2,
+        |sand| sand == ORIGIN_POINT,
+    ) {
+        f(&map);
+    }
+
+    n_sand(&map)
+}
+
+fn drop_one_sand(
+    map: &mut Map,
+    mut wall_predicate: impl FnMut(Coord) -> bool,
+    mut complete_predicate: impl FnMut(Coord) -> bool,
+) -> ControlFlow<()> {
+    let mut sand = ORIGIN_POINT;
+
+    loop {
+        let d = Point::new(sand.x, sand.y + 1);
+        let dl = d.x.checked_sub(1).map(|x| Point::new(x, d.y));
+        let dr = Point::new(d.x + 1, d.y);
+
+        let candidates = [Some(d), dl, Some(dr)];
+        match candidates
+            .into_iter()
+            .flatten()
+            .find(|&c| !(map.contains(c) || wall_predicate(c)))
+        {
+            Some(next) => sand = next,
+            None => {
+                let old_state = map.insert(sand, State::Sand);
+
+                return if old_state.is_some() {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                };
+            }
+        }
+
+        if complete_predicate(sand) {
+            return ControlFlow::Break(());
+        }
+    }
+}
+
+fn parse_map_and_max_y(s: &str) -> Result<(Map, Dim)> {
+    let map = parse_map(s).context(MapInvalidSnafu)?;
+    with_max_y(map)
+}
+
+/// Only the map itself is held in memory, which is limited by
+/// [`MAX_COORDINATE`] rather than by the length of the input.
+fn parse_map_and_max_y_from_reader(reader: impl BufRead) -> Result<(Map, Dim)> {
+    let mut map = SparseGrid::new();
+
+    for (n, l) in numbered_lines(reader) {
+        let l = l.context(ReadSnafu)?;
+        add_walls(&mut map, &l, &l)
+            .on_line(n)
+            .context(MapInvalidSnafu)?;
+    }
+
+    with_max_y(map)
+}
+
+fn with_max_y(map: Map) -> Result<(Map, Dim)> {
+    let max_y = map.bounds().context(MapNoMaxValueSnafu)?.max.y;
+    Ok((map, max_y))
+}
+
+fn parse_map(s: &str) -> ParseMapResult<Map> {
+    let mut map = SparseGrid::new();
+
+    for l in s.lines() {
+        add_walls(&mut map, s, l)?;
+    }
+
+    Ok(map)
+}
+
+/// Adds the walls described by the line `l` of the input `s`.
+fn add_walls(map: &mut Map, s: &str, l: &str) -> ParseMapResult<()> {
+    use parse_map_error::*;
+
+    let windows = l
+        .split(" -> ")
+        .map(|c| {
+            let (x, y) = c
+                .split_once(',')
+                .context(CoordinateMalformedSnafu)
+                .locate(s, c)?;
+            let x = x.parse().context(XInvalidSnafu).locate(s, x)?;
+            let y = y.parse().context(YInvalidSnafu).locate(s, y)?;
+            if x > MAX_COORDINATE || y > MAX_COORDINATE {
+                return CoordinateTooLargeSnafu.fail().locate(s, c);
+            }
+            let coord: Coord = Point::new(x, y);
+            Ok(coord)
+        })
+        .tuple_windows();
+
+    for window in windows {
+        let (start, end) = window;
+        let (start, end) = (start?, end?);
+        if start.x != end.x && start.y != end.y {
+            return DiagonalLineSnafu.fail().locate(s, l);
+        }
+
+        let line = BoundingBox::from_points([start, end])
+            .into_iter()
+            .flat_map(BoundingBox::points);
+
+        map.extend(line.map(|c| (c, State::Wall)));
+    }
+
+    Ok(())
+}
+
+fn n_sand(map: &Map) -> usize {
+    map.values().filter(|s| matches!(s, State::Sand)).count()
+}
+
+#[derive(Debug, Clone, Snafu)]
+#[snafu(module)]
+pub enum ParseMapError {
+    CoordinateMalformed,
+    XInvalid {
+        source: std::num::ParseIntError,
+    },
+    YInvalid {
+        source: std::num::ParseIntError,
+    },
+
+    #[snafu(display("Coordinates may not be larger than {MAX_COORDINATE}"))]
+    CoordinateTooLarge,
+
+    DiagonalLine,
+}
+
+type ParseMapResult<T, E = Located<ParseMapError>> = std::result::Result<T, E>;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    MapInvalid { source: Located<ParseMapError> },
+
+    MapNoMaxValue,
+
+    Read { source: std::io::Error },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    /// Paths with corners that need not line up, around where the sand
+    /// falls from.
+    const ARBITRARY_INPUT: &str = r"([0-9]{3},1?[0-9]( -> [0-9]{3},1?[0-9]){0,3}\n){0,4}";
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(24, units_of_sand_come_to_rest(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        assert_eq!(93, units_of_sand_come_to_rest_infinite_floor(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        aoc_common::generate::solve_seeds::<Day14>(5, 20)
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(s in ARBITRARY_INPUT) {
+            if let Ok(parsed) = Day14::parse(&s) {
+                let _ = Day14::part1(&parsed, Day14::PART1_PARAMS);
+                let _ = Day14::part2(&parsed, Day14::PART2_PARAMS);
+            }
+        }
+
+        #[test]
+        fn reader_matches_str(s in ARBITRARY_INPUT) {
+            // Down to the corner of a path that is malformed
+            let from_str = units_of_sand_come_to_rest(&s);
+            let from_reader = units_of_sand_come_to_rest_from_reader(s.as_bytes());
+            prop_assert_eq!(format!("{from_str:?}"), format!("{from_reader:?}"));
+        }
+    }
+}
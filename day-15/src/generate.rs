@@ -0,0 +1,65 @@
+use crate::{Coord, Day15, Dim, SEARCH_AREA_MAX};
+use aoc_common::{
+    generate::{Generate, GenerateRng, Rng},
+    Point,
+};
+use std::fmt::Write;
+
+/// `size` is the number of sensors, in addition to the four that
+/// leave only the distress beacon uncovered.
+impl Generate for Day15 {
+    fn generate(rng: &mut GenerateRng, size: usize) -> String {
+        let max = SEARCH_AREA_MAX;
+        let distress = Point::new(rng.gen_range(0..max), rng.gen_range(0..=max));
+
+        let mut sensors = vec![];
+
+        // A sensor diagonally offset by `a` in both directions, with a
+        // range of `2a - 1`, covers every point of the `2a` by `2a`
+        // square between it and the distress beacon, apart from its
+        // corners. One per quadrant covers the search area, apart from
+        // the distress beacon.
+        for (dx, dy) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+            let extent_x = if dx < 0 { distress.x } else { max - distress.x };
+            let extent_y = if dy < 0 { distress.y } else { max - distress.y };
+            let a = Dim::max(extent_x, extent_y) / 2 + 1;
+
+            let sensor = Point::new(distress.x + dx * a, distress.y + dy * a);
+            sensors.push((sensor, 2 * a - 1));
+        }
+
+        // The rest may overlap anything but the distress beacon
+        while sensors.len() < size + 4 {
+            let sensor = Point::new(rng.gen_range(0..=max), rng.gen_range(0..=max));
+            let distance = sensor.manhattan_distance(distress);
+            if distance < 2 {
+                continue;
+            }
+
+            let range = rng.gen_range(1..=Dim::min(distance - 1, max / 8));
+            sensors.push((sensor, range));
+        }
+
+        let mut s = String::new();
+
+        for (sensor, range) in sensors {
+            let beacon = beacon_at(rng, sensor, range);
+            writeln!(
+                s,
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                sensor.x, sensor.y, beacon.x, beacon.y,
+            )
+            .expect("Unable to write to a string");
+        }
+
+        s
+    }
+}
+
+fn beacon_at(rng: &mut GenerateRng, sensor: Coord, range: Dim) -> Coord {
+    let dx = rng.gen_range(0..=range);
+    let dy = range - dx;
+    let sign = |rng: &mut GenerateRng| if rng.gen() { 1 } else { -1 };
+
+    Point::new(sensor.x + sign(rng) * dx, sensor.y + sign(rng) * dy)
+}
@@ -0,0 +1,489 @@
+//! Day 15: Beacon Exclusion Zone
+//!
+//! The input lists sensors and the closest beacon each detects, by
+//! Manhattan distance. Part 1 counts the positions on one row that
+//! cannot hold a beacon; part 2 finds the one position in a square
+//! that no sensor covers.
+
+use aoc_common::{
+    abs_diff, Alternative, BoundingBox, Frame, LocateExt, Located, Palette, Point, Solution, Style,
+};
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+mod generate;
+
+/// The row part 1 examines.
+pub const INTERESTING_Y: Dim = 2_000_000;
+/// The largest coordinate of the square part 2 searches.
+pub const SEARCH_AREA_MAX: Dim = 4_000_000;
+
+/// Far beyond the real inputs, which stay within a few million of the
+/// origin, but small enough that distances between any two positions
+/// and the ranges they cover can't overflow a [`Dim`].
+const MAX_COORDINATE: Dim = 1_000_000_000;
+
+pub type Dim = i64;
+pub type Coord = Point<Dim>;
+/// Each sensor and the closest beacon it detects.
+pub type Map = BTreeMap<Coord, Coord>;
+
+/// The parsed input is the [`Map`] of sensors.
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input<'a> = Map;
+    type Error = Error;
+
+    type Part1Params = Dim;
+    type Part1 = usize;
+
+    type Part2Params = (Dim, Dim);
+    type Part2 = Dim;
+
+    const PART1_PARAMS: Self::Part1Params = INTERESTING_Y;
+    const PART2_PARAMS: Self::Part2Params = (SEARCH_AREA_MAX, SEARCH_AREA_MAX);
+
+    const EXAMPLE_PART1_PARAMS: Self::Part1Params = 10;
+    const EXAMPLE_PART2_PARAMS: Self::Part2Params = (20, 20);
+
+    fn parse(s: &str) -> Result<Self::Input<'_>> {
+        parse_map(s).context(MapMalformedSnafu)
+    }
+
+    fn part1(map: &Self::Input<'_>, interesting_y: Dim) -> Result<usize> {
+        let beacons = map.values().copied().collect::<BTreeSet<_>>();
+
+        let no_beacon_locations = areas_of_interest(map, interesting_y)
+            .filter(|c| !beacons.contains(c))
+            .collect::<BTreeSet<_>>();
+
+        Ok(no_beacon_locations.len())
+    }
+
+    fn part2(map: &Self::Input<'_>, (x_max, y_max): (Dim, Dim)) -> Result<Dim> {
+        let c = find_distress_beacon(map, x_max, y_max)?;
+        Ok(4_000_000 * c.x + c.y)
+    }
+
+    fn part1_alternatives() -> Vec<Alternative<Self, Dim, usize>> {
+        vec![("intervals", part1_intervals)]
+    }
+
+    fn part2_alternatives() -> Vec<Alternative<Self, (Dim, Dim), Dim>> {
+        vec![("intervals", part2_intervals)]
+    }
+}
+
+/// Counts the covered row a range at a time, instead of a position at
+/// a time.
+fn part1_intervals(map: &Map, interesting_y: Dim) -> Result<usize> {
+    let covered = merged_coverage(map, interesting_y);
+
+    let width = covered.iter().map(|r| r.end() - r.start() + 1).sum::<Dim>();
+    let beacons = map
+        .values()
+        .filter(|b| b.y == interesting_y && covered.iter().any(|r| r.contains(&b.x)))
+        .collect::<BTreeSet<_>>();
+
+    Ok(width as usize - beacons.len())
+}
+
+/// Looks for a gap between the covered ranges of each row.
+fn part2_intervals(map: &Map, (x_max, y_max): (Dim, Dim)) -> Result<Dim> {
+    for y in 0..=y_max {
+        let mut x = 0;
+
+        for r in merged_coverage(map, y) {
+            if *r.start() > x {
+                break;
+            }
+            x = Dim::max(x, r.end() + 1);
+        }
+
+        if x <= x_max {
+            return Ok(4_000_000 * x + y);
+        }
+    }
+
+    DistressBeaconNotFoundSnafu.fail()
+}
+
+/// The positions of row `y` that any sensor covers, as sorted ranges
+/// that neither overlap nor touch.
+fn merged_coverage(map: &Map, y: Dim) -> Vec<RangeInclusive<Dim>> {
+    let mut ranges = map
+        .iter()
+        .filter_map(|(&s, &b)| {
+            let spare = s.manhattan_distance(b) - abs_diff(y, s.y);
+            (spare >= 0).then(|| (s.x - spare)..=(s.x + spare))
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|r| *r.start());
+
+    let mut merged: Vec<RangeInclusive<Dim>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if *r.start() <= last.end() + 1 => {
+                *last = *last.start()..=Dim::max(*last.end(), *r.end());
+            }
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// How many positions on row `interesting_y` cannot contain a beacon.
+pub fn n_positions_cannot_contain_a_beacon_at_y(s: &str, interesting_y: Dim) -> Result<usize> {
+    Day15::part1(&Day15::parse(s)?, interesting_y)
+}
+
+/// The tuning frequency of the one position no sensor covers, within
+/// `0..=x_max` and `0..=y_max`.
+pub fn tuning_frequency_in_square(s: &str, x_max: Dim, y_max: Dim) -> Result<Dim> {
+    Day15::part2(&Day15::parse(s)?, (x_max, y_max))
+}
+
+/// As [`tuning_frequency_in_square`], calling `f` with the sensors and
+/// the position no sensor covers.
+pub fn tuning_frequency_in_square_with(
+    s: &str,
+    x_max: Dim,
+    y_max: Dim,
+    f: impl FnOnce(&Map, Coord),
+) -> Result<Dim> {
+    let map = Day15::parse(s)?;
+    let c = find_distress_beacon(&map, x_max, y_max)?;
+    f(&map, c);
+    Ok(4_000_000 * c.x + c.y)
+}
+
+/// What is drawn in each cell of the sensors' coverage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Coverage {
+    Covered,
+    Sensor,
+    Beacon,
+    DistressBeacon,
+}
+
+/// Covered areas in blue, leaving the gaps dark.
+pub fn palette() -> Palette<Coverage> {
+    Palette::new(Style::new('.', [0, 0, 0]))
+        .with(Coverage::Covered, Style::new('#', [32, 64, 160]))
+        .with(Coverage::Sensor, Style::new('S', [255, 255, 255]))
+        .with(Coverage::Beacon, Style::new('B', [255, 200, 0]))
+        .with(Coverage::DistressBeacon, Style::new('!', [255, 0, 0]))
+}
+
+/// The coverage is drawn at most this many cells across.
+const COVERAGE_CELLS: Dim = 400;
+
+/// The area covered by the sensors, for drawing.
+///
+/// The real input spans millions of positions, so each cell stands
+/// for a square of positions and is covered when its top-left
+/// position is.
+pub fn coverage_frame(map: &Map, distress_beacon: Option<Coord>) -> Frame<Coverage> {
+    let radii = map
+        .iter()
+        .map(|(&s, &b)| (s, s.manhattan_distance(b)))
+        .collect::<Vec<_>>();
+
+    let corners = radii
+        .iter()
+        .flat_map(|&(s, r)| [Point::new(s.x - r, s.y - r), Point::new(s.x + r, s.y + r)]);
+    let Some(bounds) = BoundingBox::from_points(corners) else {
+        return Frame::new();
+    };
+
+    let side = Dim::max(bounds.width(), bounds.height());
+    let cell = (side + COVERAGE_CELLS - 1) / COVERAGE_CELLS;
+    let to_cell = |p: Coord| {
+        Point::new(
+            (p.x - bounds.min.x).div_euclid(cell),
+            (p.y - bounds.min.y).div_euclid(cell),
+        )
+    };
+    let cells = BoundingBox {
+        min: Point::new(0, 0),
+        max: to_cell(bounds.max),
+    };
+
+    let mut frame = cells
+        .points()
+        .filter(|c| {
+            let p = Point::new(bounds.min.x + c.x * cell, bounds.min.y + c.y * cell);
+            radii.iter().any(|&(s, r)| s.manhattan_distance(p) <= r)
+        })
+        .map(|c| (c, Coverage::Covered))
+        .collect::<Frame<_>>();
+
+    frame.extend(map.values().map(|&b| (to_cell(b), Coverage::Beacon)));
+    frame.extend(map.keys().map(|&s| (to_cell(s), Coverage::Sensor)));
+    frame.extend(distress_beacon.map(|d| (to_cell(d), Coverage::DistressBeacon)));
+
+    frame
+}
+
+fn find_distress_beacon(map: &Map, x_max: Dim, y_max: Dim) -> Result<Coord> {
+    let map = map
+        .iter()
+        .map(|(&s, &b)| (s, s.manhattan_distance(b)))
+        .collect::<BTreeMap<_, _>>();
+
+    // Walk the Y axis, "casting rays" along the X axis
+    // We can calculate where we'd exit each sensor's area, speeding up the runtime
+    for y in 0..=y_max {
+        let mut x = 0;
+
+        'cast: while x <= x_max {
+            let c = Point::new(x, y);
+
+            for (&s, &radius) in &map {
+                let my_radius = s.manhattan_distance(c);
+
+                if my_radius > radius {
+                    // We are not inside this sensor's range
+                    continue;
+                }
+
+                // Calculate what X position we'd leave this sensor's area at
+                let delta_y = abs_diff(y, s.y);
+                let delta_x = abs_diff(delta_y, radius);
+                let last_point_in_area = s.x + delta_x;
+                let exit_point = last_point_in_area + 1;
+
+                if exit_point <= x {
+                    continue;
+                }
+
+                x = exit_point;
+                continue 'cast;
+            }
+
+            // We checked all the sensors but didn't make it across; this must be our location
+            return Ok(c);
+        }
+    }
+
+    DistressBeaconNotFoundSnafu.fail()
+}
+
+fn parse_map(s: &str) -> ParseMapResult<Map> {
+    s.lines()
+        .map(|l| {
+            use parse_map_error::*;
+
+            let rest = l
+                .strip_prefix("Sensor at x=")
+                .context(SensorXNotFoundSnafu)
+                .locate(s, l)?;
+            let (sx, rest) = rest
+                .split_once(", y=")
+                .context(SensorYNotFoundSnafu)
+                .locate(s, rest)?;
+            let (sy, rest) = rest
+                .split_once(": closest beacon is at x=")
+                .context(BeaconXNotFoundSnafu)
+                .locate(s, rest)?;
+            let (bx, by) = rest
+                .split_once(", y=")
+                .context(BeaconYNotFoundSnafu)
+                .locate(s, rest)?;
+
+            let sx = sx.parse().context(SensorXMalformedSnafu).locate(s, sx)?;
+            let sy = sy.parse().context(SensorYMalformedSnafu).locate(s, sy)?;
+            let bx = bx.parse().context(BeaconXMalformedSnafu).locate(s, bx)?;
+            let by = by.parse().context(BeaconYMalformedSnafu).locate(s, by)?;
+
+            for v in [sx, sy, bx, by] {
+                if Dim::abs(v) > MAX_COORDINATE {
+                    return CoordinateTooLargeSnafu.fail().locate(s, l);
+                }
+            }
+
+            Ok((Point::new(sx, sy), Point::new(bx, by)))
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseMapError {
+    SensorXNotFound,
+
+    SensorYNotFound,
+
+    BeaconXNotFound,
+
+    BeaconYNotFound,
+
+    SensorXMalformed {
+        source: std::num::ParseIntError,
+    },
+
+    SensorYMalformed {
+        source: std::num::ParseIntError,
+    },
+
+    BeaconXMalformed {
+        source: std::num::ParseIntError,
+    },
+
+    BeaconYMalformed {
+        source: std::num::ParseIntError,
+    },
+
+    #[snafu(display("Coordinates may not be further than {MAX_COORDINATE} from the origin"))]
+    CoordinateTooLarge,
+}
+
+type ParseMapResult<T, E = Located<ParseMapError>> = std::result::Result<T, E>;
+
+fn areas_of_interest(map: &Map, interesting_y: Dim) -> impl Iterator<Item = Coord> + '_ {
+    map.iter().flat_map(move |(&s, &b)| {
+        let distance = s.manhattan_distance(b);
+
+        let x_min = s.x - distance;
+        let x_max = s.x + distance;
+        let y_min = s.y - distance;
+        let y_max = s.y + distance;
+
+        let x = x_min..=x_max;
+        let y = y_min..=y_max;
+        let y = intersect(y, interesting_y..=interesting_y);
+
+        let square = x.cartesian_product(y).map(Point::from);
+        square
+            .map(move |c| {
+                let d = s.manhattan_distance(c);
+                (c, d)
+            })
+            .filter_map(move |(c, d)| (d <= distance).then_some(c))
+    })
+}
+
+fn intersect<T>(a: RangeInclusive<T>, b: RangeInclusive<T>) -> RangeInclusive<T>
+where
+    T: Ord,
+{
+    let (ax, ay) = a.into_inner();
+    let (bx, by) = b.into_inner();
+
+    let x = T::max(ax, bx);
+    let y = T::min(ay, by);
+
+    x..=y
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    MapMalformed {
+        source: Located<ParseMapError>,
+    },
+
+    #[snafu(display("Every position in the search area is covered by a sensor"))]
+    DistressBeaconNotFound,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = include_str!("../input.test");
+
+    #[test]
+    #[snafu::report]
+    fn example() -> Result<()> {
+        assert_eq!(
+            26,
+            n_positions_cannot_contain_a_beacon_at_y(INPUT, Day15::EXAMPLE_PART1_PARAMS)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_part2() -> Result<()> {
+        let (x_max, y_max) = Day15::EXAMPLE_PART2_PARAMS;
+        assert_eq!(56000011, tuning_frequency_in_square(INPUT, x_max, y_max)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn generated() -> Result<()> {
+        // Part 1 takes far too long to run unoptimized at the real scale
+        let input = aoc_common::generate::generate::<Day15>(0, 20);
+        let parsed = Day15::parse(&input)?;
+        Day15::part2(&parsed, Day15::PART2_PARAMS)?;
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn alternatives_agree() -> Result<()> {
+        let parsed = Day15::parse(INPUT)?;
+        let (y, area) = (Day15::EXAMPLE_PART1_PARAMS, Day15::EXAMPLE_PART2_PARAMS);
+        let part1 = Day15::part1(&parsed, y)?;
+        let part2 = Day15::part2(&parsed, area)?;
+
+        for (name, f) in Day15::part1_alternatives() {
+            assert_eq!(part1, f(&parsed, y)?, "{name}");
+        }
+        for (name, f) in Day15::part2_alternatives() {
+            assert_eq!(part2, f(&parsed, area)?, "{name}");
+        }
+        Ok(())
+    }
+
+    /// Lines of mostly small coordinates, so the parts have something
+    /// to search, mixed with ones from anywhere in a [`Dim`].
+    fn arbitrary_input() -> impl Strategy<Value = String> {
+        let coordinate = prop_oneof![-99..=99_i64, any::<Dim>()];
+        let line = [(); 4].map(|()| coordinate.clone());
+
+        prop::collection::vec(line, 0..=5).prop_map(|lines| {
+            lines
+                .into_iter()
+                .map(|[sx, sy, bx, by]| {
+                    format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}\n")
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn alternatives_agree_on_arbitrary_maps(s in r"(Sensor at x=[0-9], y=[0-9]: closest beacon is at x=[0-9], y=[0-9]\n){0,5}") {
+            let parsed = Day15::parse(&s).unwrap();
+            let part1 = Day15::part1(&parsed, 4).ok();
+            let part2 = Day15::part2(&parsed, (9, 9)).ok();
+
+            for (name, f) in Day15::part1_alternatives() {
+                prop_assert_eq!(part1, f(&parsed, 4).ok(), "{}", name);
+            }
+            for (name, f) in Day15::part2_alternatives() {
+                prop_assert_eq!(part2, f(&parsed, (9, 9)).ok(), "{}", name);
+            }
+        }
+
+        #[test]
+        fn never_panics(s in arbitrary_input()) {
+            if let Ok(parsed) = Day15::parse(&s) {
+                let _ = Day15::part1(&parsed, Day15::EXAMPLE_PART1_PARAMS);
+                let _ = Day15::part2(&parsed, Day15::EXAMPLE_PART2_PARAMS);
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day01::parse(s) {
+        let _ = Day01::part1(&parsed, Day01::PART1_PARAMS);
+        let _ = Day01::part2(&parsed, Day01::PART2_PARAMS);
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_03::Day03;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day03::parse(s) {
+        let _ = Day03::part1(&parsed, Day03::PART1_PARAMS);
+        let _ = Day03::part2(&parsed, Day03::PART2_PARAMS);
+    }
+});
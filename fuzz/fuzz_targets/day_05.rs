@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day05::parse(s) {
+        let _ = Day05::part1(&parsed, Day05::PART1_PARAMS);
+        let _ = Day05::part2(&parsed, Day05::PART2_PARAMS);
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_07::Day07;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day07::parse(s) {
+        let _ = Day07::part1(&parsed, Day07::PART1_PARAMS);
+        let _ = Day07::part2(&parsed, Day07::PART2_PARAMS);
+    }
+});
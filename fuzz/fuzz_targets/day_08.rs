@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_08::Day08;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day08::parse(s) {
+        let _ = Day08::part1(&parsed, Day08::PART1_PARAMS);
+        let _ = Day08::part2(&parsed, Day08::PART2_PARAMS);
+    }
+});
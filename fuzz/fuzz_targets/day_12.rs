@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_12::Day12;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day12::parse(s) {
+        let _ = Day12::part1(&parsed, Day12::PART1_PARAMS);
+        let _ = Day12::part2(&parsed, Day12::PART2_PARAMS);
+    }
+});
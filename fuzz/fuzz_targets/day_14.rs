@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_14::Day14;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    if let Ok(parsed) = Day14::parse(s) {
+        let _ = Day14::part1(&parsed, Day14::PART1_PARAMS);
+        let _ = Day14::part2(&parsed, Day14::PART2_PARAMS);
+    }
+});
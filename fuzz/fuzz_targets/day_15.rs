@@ -0,0 +1,20 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_15::Day15;
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs make each run slower without finding new problems.
+const MAX_LEN: usize = 1024;
+
+fuzz_target!(|s: &str| {
+    if s.len() > MAX_LEN {
+        return;
+    }
+
+    // The real row and search area take far too long for each run
+    if let Ok(parsed) = Day15::parse(s) {
+        let _ = Day15::part1(&parsed, Day15::EXAMPLE_PART1_PARAMS);
+        let _ = Day15::part2(&parsed, Day15::EXAMPLE_PART2_PARAMS);
+    }
+});
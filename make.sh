@@ -1,5 +0,0 @@
-#!/bin/bash
-
-set -eu
-
-cargo generate --path template --vcs none --name $1